@@ -0,0 +1,33 @@
+//! Compares `zeroed_arr!` with a `[0; N]` literal. Run with
+//! `cargo bench --bench zeroed`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use array_lit::{arr, zeroed_arr};
+
+const N: usize = 1 << 16;
+const ITERATIONS: u32 = 20_000;
+
+#[inline(never)]
+fn literal() -> [u32; N] {
+    arr![0; N; { 0: 1 }]
+}
+
+#[inline(never)]
+fn zeroed() -> [u32; N] {
+    zeroed_arr![u32; N; { 0: 1 }]
+}
+
+fn measure(f: fn() -> [u32; N]) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(f());
+    }
+    start.elapsed() / ITERATIONS
+}
+
+fn main() {
+    println!("[0; {}]      {:?}", N, measure(literal));
+    println!("zeroed_arr!  {:?}", measure(zeroed));
+}
@@ -0,0 +1,75 @@
+//! A builder for arrays whose entries are only known at runtime.
+
+use core::ops::{Bound, RangeBounds};
+
+/// Builds an array step by step, without a macro.
+///
+/// This is useful when the overrides are computed in a loop, or come from
+/// some other data structure. Each method applies its entries immediately,
+/// so later calls override earlier ones, like entries in
+/// [`arr!`](../macro.arr.html).
+///
+/// Indices that are out of bounds cause a panic.
+///
+/// ```
+/// use array_lit::{arr, builder::ArrayBuilder};
+///
+/// let a = ArrayBuilder::new(0).set_range(1..4, 2).set(3, 5).build();
+/// assert_eq!(a, arr![0; 5; { 1..3: 2, 3: 5 }]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArrayBuilder<T, const N: usize> {
+    arr: [T; N],
+}
+
+impl<T: Clone, const N: usize> ArrayBuilder<T, N> {
+    /// Creates a builder where every element is a clone of `value`.
+    pub fn new(value: T) -> Self {
+        ArrayBuilder {
+            arr: crate::__clone_array(value),
+        }
+    }
+
+    /// Sets every element to a clone of `value`.
+    pub fn fill(mut self, value: T) -> Self {
+        self.arr.fill(value);
+        self
+    }
+
+    /// Sets every element in `range` to a clone of `value`.
+    ///
+    /// An empty range doesn't change anything.
+    pub fn set_range<R: RangeBounds<usize>>(mut self, range: R, value: T) -> Self {
+        let bounds: (Bound<usize>, Bound<usize>) =
+            (range.start_bound().cloned(), range.end_bound().cloned());
+        self.arr[bounds].fill(value);
+        self
+    }
+}
+
+impl<T, const N: usize> ArrayBuilder<T, N> {
+    /// Sets the element at `index` to `value`.
+    pub fn set(mut self, index: usize, value: T) -> Self {
+        self.arr[index] = value;
+        self
+    }
+
+    /// Returns the finished array.
+    pub fn build(self) -> [T; N] {
+        self.arr
+    }
+}
+
+impl<T: Default, const N: usize> Default for ArrayBuilder<T, N> {
+    fn default() -> Self {
+        ArrayBuilder {
+            arr: core::array::from_fn(|_| T::default()),
+        }
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for ArrayBuilder<T, N> {
+    fn from(arr: [T; N]) -> Self {
+        ArrayBuilder { arr }
+    }
+}
@@ -0,0 +1,131 @@
+//! An iterator that applies overrides lazily, without creating an array.
+
+use core::fmt;
+use core::iter::{FusedIterator, Peekable};
+
+/// Yields the items of a base iterator, except at the indices of the
+/// overrides, where it yields the value of the override instead.
+///
+/// This produces the same sequence as [`vec!`](../macro.vec.html) with
+/// single-index entries, but only computes the items that are actually read,
+/// so the base iterator can be infinite.
+///
+/// The overrides must be sorted by index. If an index appears more than once,
+/// the last value wins, like in a macro. Overrides that are out of order, or
+/// past the end of the base iterator, are ignored.
+///
+/// ```
+/// use array_lit::iter::OverrideIter;
+/// use std::iter::repeat;
+///
+/// let mut iter = OverrideIter::new(repeat(0), vec![(2, 5), (1_000_000, 7)]);
+/// assert_eq!(iter.nth(2), Some(5));
+/// assert_eq!(iter.nth(999_996), Some(0));
+/// assert_eq!(iter.next(), Some(7));
+/// ```
+pub struct OverrideIter<I, O: Iterator> {
+    base: I,
+    overrides: Peekable<O>,
+    index: usize,
+}
+
+impl<I, O> OverrideIter<I, O>
+where
+    I: Iterator,
+    O: Iterator<Item = (usize, I::Item)>,
+{
+    /// Creates an iterator that yields the items of `base`, with the values in
+    /// `overrides` at their indices.
+    pub fn new<P>(base: I, overrides: P) -> Self
+    where
+        P: IntoIterator<IntoIter = O>,
+    {
+        OverrideIter {
+            base,
+            overrides: overrides.into_iter().peekable(),
+            index: 0,
+        }
+    }
+
+    /// Removes all overrides up to `index`, and returns the last one with
+    /// exactly this index.
+    fn take_override(&mut self, index: usize) -> Option<I::Item> {
+        let mut value = None;
+        while let Some(&(i, _)) = self.overrides.peek() {
+            if i > index {
+                break;
+            }
+            let (i, v) = self.overrides.next().unwrap();
+            if i == index {
+                value = Some(v);
+            }
+        }
+        value
+    }
+}
+
+impl<I, O> Iterator for OverrideIter<I, O>
+where
+    I: Iterator,
+    O: Iterator<Item = (usize, I::Item)>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let item = self.base.next()?;
+        let index = self.index;
+        self.index += 1;
+        Some(self.take_override(index).unwrap_or(item))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<I::Item> {
+        let item = self.base.nth(n)?;
+        let index = self.index + n;
+        self.index = index + 1;
+        Some(self.take_override(index).unwrap_or(item))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.base.size_hint()
+    }
+}
+
+impl<I, O> ExactSizeIterator for OverrideIter<I, O>
+where
+    I: ExactSizeIterator,
+    O: Iterator<Item = (usize, I::Item)>,
+{
+}
+
+impl<I, O> FusedIterator for OverrideIter<I, O>
+where
+    I: FusedIterator,
+    O: Iterator<Item = (usize, I::Item)>,
+{
+}
+
+impl<I: Clone, O: Iterator + Clone> Clone for OverrideIter<I, O>
+where
+    O::Item: Clone,
+{
+    fn clone(&self) -> Self {
+        OverrideIter {
+            base: self.base.clone(),
+            overrides: self.overrides.clone(),
+            index: self.index,
+        }
+    }
+}
+
+impl<I: fmt::Debug, O: Iterator + fmt::Debug> fmt::Debug for OverrideIter<I, O>
+where
+    O::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OverrideIter")
+            .field("base", &self.base)
+            .field("overrides", &self.overrides)
+            .field("index", &self.index)
+            .finish()
+    }
+}
@@ -15,6 +15,16 @@
 //! assert_eq!(a, [1, 2, 3, 0, 0, 0, 0, 0]);
 //! ```
 //!
+//! Values at the start can also be listed before the length, followed by the
+//! value for the rest of the array. There may be fewer values than the length,
+//! but not more:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![1, 2, 3; 8; 0];
+//! assert_eq!(a, [1, 2, 3, 0, 0, 0, 0, 0]);
+//! ```
+//!
 //! The square brackets are only needed when specifying multiple consecutive
 //! elements:
 //!
@@ -36,10 +46,395 @@
 //! assert_eq!(a, [0, 0, 3, 4, 0, 0, 1, 5]);
 //! ```
 //!
+//! Values can be any expression, including casts, method calls and `if`
+//! expressions, without extra parentheses:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let flag = true;
+//! let a = arr![0u16; 4; { 0: 5u8 as u16, 1: b'a'.into(), 2: if flag { 1 } else { 2 } }];
+//! assert_eq!(a, [5, 97, 1, 0]);
+//! ```
+//!
+//! If the element type can't be inferred, it can be written after the block,
+//! instead of annotating the variable:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![0; 8; { 0: 1 } : i64];
+//! assert_eq!(a[0].leading_zeros(), 63);
+//! ```
+//!
+//! The length can be any constant expression, such as a `const fn` call or
+//! arithmetic:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![0u8; core::mem::size_of::<u64>(); { 0: 1 }];
+//! assert_eq!(a, [1, 0, 0, 0, 0, 0, 0, 0]);
+//! ```
+//!
+//! Empty entries are ignored, so a block may start with a comma or contain
+//! several commas in a row. This is convenient for generated code:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![0; 4; { , 0: 1,, 3: 2, }];
+//! assert_eq!(a, [1, 0, 0, 2]);
+//! ```
+//!
+//! Several indices can share a value by separating them with `|`. The value is
+//! only evaluated once, and then cloned for each index:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![0; 8; { 1 | 3 | 6: 9 }];
+//! assert_eq!(a, [0, 9, 0, 9, 0, 0, 9, 0]);
+//! ```
+//!
+//! A range of indices sets every element in the range to the same value.
+//! Both exclusive and inclusive ranges are supported. Empty ranges don't do
+//! anything:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![9; 8; { 2..5: 0, 6..6: 1 }];
+//! assert_eq!(a, [9, 9, 0, 0, 0, 9, 9, 9]);
+//!
+//! let b = arr![0; 8; { 2..=4: 7, 6..=5: 1 }];
+//! assert_eq!(b, [0, 0, 7, 7, 7, 0, 0, 0]);
+//! ```
+//!
+//! A range without a start begins at index 0, and a range without an end
+//! continues until the end of the array. Like other ranges, `..=end` panics if
+//! `end` is out of bounds:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![0; 8; { ..3: 1, 5..: 2 }];
+//! assert_eq!(a, [1, 1, 1, 0, 0, 2, 2, 2]);
+//!
+//! let b = arr![0; 4; { ..=0: 1, 2..: 2 }];
+//! assert_eq!(b, [1, 0, 2, 2]);
+//! ```
+//!
+//! A step can be added after a semicolon to only set every n-th element of
+//! the range. A step of zero panics:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![0; 8; { 0..8;2: 1, 1..8;3: 2 }];
+//! assert_eq!(a, [1, 2, 1, 0, 2, 0, 1, 2]);
+//! ```
+//!
+//! With `rev` in front of a range, the elements are set in reverse order,
+//! starting with the last one. This matters when the value has side effects.
+//! The same elements are set as without `rev`, also when there is a step:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let mut n = 0;
+//! let a = arr![0; 8; { rev 0..8: { n += 1; n } }];
+//! assert_eq!(a, [8, 7, 6, 5, 4, 3, 2, 1]);
+//!
+//! let mut n = 0;
+//! let b = arr![0; 8; { rev 1..8;3: { n += 1; n } }];
+//! assert_eq!(b, [0, 3, 0, 0, 2, 0, 0, 1]);
+//! ```
+//!
+//! The value of a range can also be computed from each index, by writing a
+//! closure after `fn`. It is called with the index of every element that is
+//! set, also when there is a step:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![0; 8; { 0..8;2: fn |i| i * 10, 7: fn |i| i }];
+//! assert_eq!(a, [0, 0, 20, 0, 40, 0, 60, 7]);
+//! ```
+//!
+//! A range can be restricted with a guard after `where`, which is a closure or
+//! function that is called with each index. Elements are only set where it
+//! returns `true`:
+//!
+//! ```
+//! # use array_lit::arr;
+//! fn is_prime(n: usize) -> bool {
+//!     matches!(n, 2 | 3 | 5 | 7)
+//! }
+//!
+//! let a = arr![0; 8; { 0..8 where is_prime: 1, 2.. where |i| i % 3 == 0: 2 }];
+//! assert_eq!(a, [0, 0, 1, 2, 0, 1, 2, 1]);
+//! ```
+//!
+//! To decide based on the current value of an element instead, write a
+//! closure after `if`. It is called with a reference to each element before
+//! it is set:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![0; 6; { 1: 5, 3: 7, 0..6 if |x| *x == 0: 1 }];
+//! assert_eq!(a, [1, 5, 1, 7, 1, 1]);
+//! ```
+//!
+//! Indices and the bounds of a range can be any expression, such as an enum
+//! discriminant in a lookup table. Expressions that contain `..`, `|` or `;`
+//! need parentheses:
+//!
+//! ```
+//! # use array_lit::arr;
+//! #[repr(usize)]
+//! enum Color { Red, Green, Blue }
+//!
+//! let a = arr![0; 3; { Color::Red as usize: 1, Color::Green as usize..3: 2 }];
+//! assert_eq!(a, [1, 2, 2]);
+//! ```
+//!
+//! A range that is only known at runtime, or any other iterator of indices,
+//! can be used with `in`:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let range = 1..3;
+//! let a = arr![0; 5; { in range: 1, in (vec![0, 4]): 2 }];
+//! assert_eq!(a, [2, 1, 1, 0, 2]);
+//! ```
+//!
+//! Entries that are only known at runtime can be given as an iterator of
+//! `(index, value)` pairs with `from_pairs`. Like other indices, an index that
+//! is out of bounds panics:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let pairs = vec![(1, 5), (3, 7)];
+//! let a = arr![0; 5; { from_pairs pairs, 0: 1 }];
+//! assert_eq!(a, [1, 5, 0, 7, 0]);
+//! ```
+//!
+//! Symmetric arrays only need their first half. `mirror` copies it into the
+//! second half in reverse order; if the length is odd, the middle element is
+//! left as it is. Entries after `mirror` are applied afterwards:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![0; 8; { [0]: [1, 2, 3, 4], mirror }];
+//! assert_eq!(a, [1, 2, 3, 4, 4, 3, 2, 1]);
+//! let b = arr![0; 5; { [0]: [1, 2, 3], mirror, 0: 9 }];
+//! assert_eq!(b, [9, 2, 3, 2, 1]);
+//! ```
+//!
+//! A range that is stored in a constant or variable can't be used as an index
+//! directly, because the macro can't see that it is a range. Use `range`
+//! instead, which accepts any `Range<usize>` in parentheses or as a single
+//! identifier:
+//!
+//! ```
+//! # use array_lit::arr;
+//! use std::ops::Range;
+//!
+//! const HEADER: Range<usize> = 0..3;
+//! let body = 4..6;
+//! let a = arr![0; 6; { range HEADER: 1, range body: 2 }];
+//! assert_eq!(a, [1, 1, 1, 0, 2, 2]);
+//! ```
+//!
+//! Indices can also be counted from the end with `^`, where `^0` is the last
+//! element, `^1` the second-to-last, and so on:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![0; 5; { ^0: 9, ^1: 8 }];
+//! assert_eq!(a, [0, 0, 0, 8, 9]);
+//! ```
+//!
+//! This panics if the offset is not smaller than the length.
+//!
+//! With `clamp`, an index past the end is replaced by the index of the last
+//! element instead of panicking. If the array is empty, the entry is skipped,
+//! and its value isn't evaluated:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let i = 10;
+//! let a = arr![0; 4; { clamp i: 1, clamp 1: 2 }];
+//! assert_eq!(a, [0, 2, 0, 1]);
+//! ```
+//!
+//! Similarly, with `wrap`, an index past the end wraps around to the start, like
+//! in a ring buffer. The index is taken modulo the length, and nothing is
+//! assigned if the array is empty:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let head = 3;
+//! let a = arr![0; 4; { wrap (head + 1): 1, wrap (head + 2): 2 }];
+//! assert_eq!(a, [1, 2, 0, 0]);
+//! ```
+//!
 //! The familiar array syntax (`arr![a; N]` and `arr![a, b, c]`) is also
 //! supported, so the `vec!` macro from this crate is a drop-in replacement for
 //! `std::vec!`.
 //!
+//! The `repeat` keyword fills the whole array with a repeating pattern. If the
+//! pattern doesn't fit evenly, the last repetition is cut off:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![0; 7; { repeat: [1, 2, 3] }];
+//! assert_eq!(a, [1, 2, 3, 1, 2, 3, 1]);
+//! ```
+//!
+//! To repeat a list of values only a certain number of times, multiply it by
+//! the number of repetitions. The values are inserted one after another,
+//! starting at the given index:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![0; 7; { [1]: [1, 2] * 3 }];
+//! assert_eq!(a, [0, 1, 2, 1, 2, 1, 2]);
+//! ```
+//!
+//! To insert the same values at several positions, list all start indices in
+//! the brackets. The values are inserted at each start in order, so where the
+//! copies overlap, the last one wins:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![0; 8; { [0, 4]: [1, 2], [5, 6]: [3] }];
+//! assert_eq!(a, [1, 2, 0, 0, 1, 3, 3, 0]);
+//! ```
+//!
+//! If all indices are integer literals, the length can be inferred with `_`.
+//! The array is then just long enough to contain all the specified elements:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![0; _; { 1: 1, [3]: [2, 3] }];
+//! assert_eq!(a, [0, 1, 0, 2, 3]);
+//! ```
+//!
+//! Otherwise, this produces a compile error:
+//!
+//! ```compile_fail
+//! # use array_lit::arr;
+//! let i = 4;
+//! let a = arr![0; _; { 1: 1, i: 2 }];
+//! ```
+//!
+//! Integer literals can be written in any base and with underscores, such as
+//! `0x10`, `0b1010`, `0o17` or `1_000`. They are compared by their value:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![0; _; { 0x4: 1, [0b1]: [2, 3] }];
+//! assert_eq!(a, [0, 2, 3, 0, 1]);
+//! ```
+//!
+//! When both the length and an index are integer literals, an index that is
+//! out of bounds is detected at compile time:
+//!
+//! ```compile_fail
+//! # use array_lit::arr;
+//! let a = arr![0; 4; { 7: 1 }];
+//! ```
+//!
+//! Indices that aren't literals are checked at runtime, like in regular
+//! indexing expressions. In debug builds, the panic message of an array, slice
+//! or `Vec` then includes the index and the length, e.g. `array-lit: index 7
+//! out of bounds for len 4`.
+//!
+//! An index that is computed, such as `(base + offset)`, can overflow. This
+//! panics in debug builds and wraps around in release builds, so the wrong
+//! element may be assigned. With the `checked-indices` feature, `+`, `-` and
+//! `*` in an index in parentheses are evaluated with `checked_add`,
+//! `checked_sub` and `checked_mul` instead, and an overflow always panics with
+//! `array-lit: index overflow`. This applies to single indices and indices
+//! separated by `|`, and also works in `const_arr!`:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let (base, offset) = (2, 3);
+//! let a = arr![0; 8; { (base + offset * 2 - 1): 1 }];
+//! assert_eq!(a[7], 1);
+//! ```
+//!
+//! Entries that overlap are applied in order, so later entries overwrite
+//! earlier ones. Since this is easy to get wrong in large tables, the
+//! `check-overlap` feature turns overlapping entries into a compile error.
+//! This only checks entries whose indices and lengths are integer literals.
+//!
+//! With the `sorted-indices` feature, overlapping entries are instead applied
+//! in the order of their first index, so entries starting at a lower index are
+//! overwritten by entries starting at a higher index, regardless of where they
+//! appear in the block. Entries with the same first index are still applied in
+//! source order. This also only works if all indices are integer literals,
+//! and has no effect on `const_arr!`.
+//!
+//! ```
+//! # #[cfg(all(feature = "sorted-indices", not(feature = "check-overlap")))] {
+//! # use array_lit::arr;
+//! let a = arr![0; 5; { 2: 7, 0..5: 1 }];
+//! assert_eq!(a, [1, 1, 7, 1, 1]);
+//! # }
+//! ```
+//!
+//! Values of elements that would be overwritten are not computed at all.
+//!
+//! To catch the same index being assigned twice without enabling a feature for
+//! the whole crate, use `arr_strict!`. It accepts the same syntax as `arr!`, but
+//! fails to compile if a literal index appears more than once. Other entries,
+//! such as ranges, aren't checked:
+//!
+//! ```
+//! # use array_lit::arr_strict;
+//! let a = arr_strict![0; 4; { 0: 1, 2 | 3: 5 }];
+//! assert_eq!(a, [1, 0, 5, 5]);
+//! ```
+//!
+//! ## Grouping entries
+//!
+//! Entries can be grouped with `base`, followed by an offset and a block.
+//! The indices in the block are relative to the offset, so a region of the
+//! array can be moved by changing a single number:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![0; 8; { 0: 1, base 4 { 0: 2, 2..4: 3 } }];
+//! assert_eq!(a, [1, 0, 0, 0, 2, 0, 3, 3]);
+//! ```
+//!
+//! Groups can be nested. They aren't supported by `const_arr!`.
+//!
+//! ## Conditional entries
+//!
+//! Entries can have attributes. This is mostly useful with `#[cfg]`, to include
+//! an entry only on some platforms or with some features:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![0; 4; {
+//!     #[cfg(target_endian = "little")] 0: 1,
+//!     #[cfg(target_endian = "big")] 3: 1,
+//! }];
+//! assert_eq!(a.iter().sum::<i32>(), 1);
+//! ```
+//!
+//! ## Labels
+//!
+//! An entry can start with a string literal, which is ignored. This can make
+//! large tables easier to read:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![0; 8; {
+//!     "header" [0]: [1, 2, 3, 4],
+//!     "checksum" ^0: 9,
+//! }];
+//! assert_eq!(a, [1, 2, 3, 4, 0, 0, 0, 9]);
+//! ```
+//!
+//! Since a label followed by `..` looks like a range, a range after a label must
+//! have a start, such as `0..4` instead of `..4`.
+//!
 //! ## How does it work?
 //!
 //! The macros generate a block that first creates a array or `Vec`, and then
@@ -57,6 +452,15 @@
 //! };
 //! ```
 //!
+//! Since the block evaluates to a normal array, it can be destructured right
+//! away:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let [a, b, ..] = arr![0; 4; { 0: 1, 3: 2 }];
+//! assert_eq!((a, b), (1, 0));
+//! ```
+//!
 //! If an array is inserted that is not of the form `[a, b, c, ..]`, a loop is
 //! used:
 //!
@@ -76,7 +480,8 @@
 //! };
 //! ```
 //!
-//! This even works for slices, arrays and `Vec`s created at runtime:
+//! This even works for slices, arrays and `Vec`s created at runtime. They are
+//! copied with `copy_from_slice`, which is faster than copying each element:
 //!
 //! ```
 //! # use array_lit::arr;
@@ -84,80 +489,588 @@
 //! arr![4; 10; { [1]: my_slice }];
 //! ```
 //!
-//! ## What about array lifetimes?
+//! If only part of a slice is copied, its length can be written after `of`.
+//! It must be a constant. The length of the sub-slice is then checked once, and
+//! it is copied as an array of that length:
 //!
-//! In trivial cases such as `arr![3; 5]`, the `'static` lifetime is inferred
-//! for array literals. This means that they have the exact same behavior as
-//! normal array literals.
+//! ```
+//! # use array_lit::arr;
+//! let src = [1, 2, 3, 4, 5, 6, 7, 8];
+//! let a = arr![0; 6; { [2]: src[4..8] of 4 }];
+//! assert_eq!(a, [0, 0, 5, 6, 7, 8]);
+//! ```
 //!
-//! In the other cases, the `'static` lifetime is not inferred. This means that
-//! the array literal is computed at runtime and doesn't have a fixed memory
-//! location. It also means that the following
+//! If the sub-slice has a different length, this panics.
 //!
-//! ```compile_fail
+//! Inserting with `[i]` panics if the inserted elements don't fit into the
+//! array. To copy only the elements that fit and ignore the rest, write
+//! `[i..]` instead. This is useful for copying a slice of unknown length into a
+//! fixed buffer:
+//!
+//! ```
 //! # use array_lit::arr;
-//! fn return_temporary() -> &'static [i32; 4] {
-//!     &arr![0; 4; { 0: 1 }]
-//! }
+//! let input = "hello world".as_bytes();
+//! let a = arr![0; 8; { [3..]: input }];
+//! assert_eq!(&a, b"\0\0\0hello");
 //! ```
 //!
-//! produces `error[E0515]: cannot return reference to temporary value`. This
-//! can be solved by assigning the literal to a `const` or `static` variable
-//! first:
+//! A range can also be filled with a copy of another part of the same array,
+//! by writing `from` and the index where the copied part starts. This uses
+//! `copy_within`, so the element type must implement `Copy`, and the two parts
+//! may overlap:
 //!
 //! ```
 //! # use array_lit::arr;
-//! fn return_temporary() -> &'static [i32; 4] {
-//!     static ARR: &[i32; 4] = &arr![0; 4; { 0: 1 }];
-//!     ARR
-//! }
+//! let a = arr![0; 8; { [0]: [1, 2, 3, 4], 4..8: from 0 }];
+//! assert_eq!(a, [1, 2, 3, 4, 1, 2, 3, 4]);
 //! ```
 //!
-//! Values assigned to a `static` or `const` variable must be constant. Due to
-//! limitations in the compiler, macros that expand to loops aren't allowed
-//! there:
+//! By default, every range expands to its own loop in the generated code. With
+//! the `outline-loops` feature, ranges are instead filled by a function of this
+//! crate that isn't generic, so there is only one copy of the loop, and the
+//! value is passed to it in a closure. The elements are then set through a
+//! function pointer, and `?` or `return` can't be used in values of ranges.
+//! `const_arr!` always uses loops.
+//!
+//! ## Computing elements from their index
+//!
+//! Instead of a single value, you can provide a closure after the `fn`
+//! keyword. It is called with each index to produce the element at that
+//! position:
 //!
-//! ```compile_fail
-//! const ARR: [i32; 4] = arr![0; 16; { [0]: [1; 8] }];
-//! // this is expanded to a loop ~~~~~~~~~~~^^^^^^
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![fn |i| i * i; 6];
+//! assert_eq!(a, [0, 1, 4, 9, 16, 25]);
 //! ```
 //!
-//! Note that `const` enforces **const evaluation**, which means that the whole
-//! array is included in the application binary. This might not be desirable if
-//! the array is large.
+//! Since no value is repeated, this also works for element types that don't
+//! implement `Copy` or `Clone`.
 //!
-//! ## Usage
+//! Specific elements can be overridden as usual. The closure is not called
+//! for them again afterwards, but it is called for every index first:
 //!
-//! Import the macros with
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![fn |i| i * 10; 5; { 0: 1, 3..: 0 }];
+//! assert_eq!(a, [1, 10, 20, 0, 0]);
+//! ```
 //!
-//! ```ignore
-//! use array_lit::{arr, vec};
+//! The `default` keyword fills the array with `Default::default()`. The
+//! default value is created for every element, so the element type doesn't
+//! need to implement `Copy`:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let a: [String; 3] = arr![default; 3; { 1: "hello".to_string() }];
+//! assert_eq!(a, ["", "hello", ""]);
 //! ```
 //!
-//! If you don't want to shadow `std::vec!`, you can rename the macro:
+//! Similarly, an expression after the `each` keyword is evaluated once for
+//! every element:
 //!
-//! ```ignore
-//! use array_lit::{arr, vec as vector};
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![each String::from("a"); 3; { 1: "b".to_string() }];
+//! assert_eq!(a, ["a", "b", "a"]);
 //! ```
 //!
-//! Importing the macros globally is also supported, although not recommended:
+//! With the `clone` keyword, the expression is evaluated only once, and the
+//! value is cloned into the other elements. For `N` elements, it is cloned
+//! `N - 1` times, because the value itself is moved into the last element:
 //!
-//! ```ignore
-//! #[macro_use]
-//! extern crate array_lit;
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![clone vec![1, 2]; 3; { 1: vec![] }];
+//! assert_eq!(a, [vec![1, 2], vec![], vec![1, 2]]);
 //! ```
 //!
-//! ## Custom indices
+//! ## Arrays without a fill value
 //!
-//! If you want to use your own `Index`/`IndexMut` implementation in these
-//! macros, you probably need an extra pair of parentheses:
+//! If every element is assigned explicitly, the fill value can be omitted with
+//! `arr_uninit!`. The element type then doesn't need to implement `Copy`,
+//! `Clone` or `Default`:
 //!
-//! ```ignore
-//! #[derive(Copy, Clone)]
-//! struct S(bool);
+//! ```
+//! # use array_lit::arr_uninit;
+//! let a = arr_uninit![2; { 1: String::from("b"), 0: String::from("a") }];
+//! assert_eq!(a, ["a", "b"]);
+//! ```
 //!
-//! /// Your custom index
-//! struct Idx(usize);
+//! All indices must be literals, and every index must be assigned exactly once.
+//! Otherwise, compilation fails:
+//!
+//! ```compile_fail
+//! # use array_lit::arr_uninit;
+//! let a = arr_uninit![3; { 0: 'a', 2: 'c' }];
+//! ```
+//!
+//! The fill value can also be written as `_` if the first entry is `..`, which
+//! covers every index. The value of this entry, or the closure after `fn`, is
+//! evaluated once per element, and later entries override it as usual. The
+//! array is built from uninitialized memory, so no fill value is ever created:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![_; 4; { ..: fn |i| i.to_string(), 0: String::new() }];
+//! assert_eq!(a, ["", "1", "2", "3"]);
+//! ```
+//!
+//! Coverage is checked when the macro is expanded, so any other first entry
+//! is a compile error, even if it happens to cover the whole array:
+//!
+//! ```compile_fail
+//! # use array_lit::arr;
+//! let a = arr![_; 2; { 0..2: String::new() }];
+//! ```
+//!
+//! ## Zeroed arrays
+//!
+//! `zeroed_arr!` takes the element type instead of a fill value, and creates an
+//! array where every byte is zero before the entries are applied:
+//!
+//! ```
+//! # use array_lit::zeroed_arr;
+//! let a = zeroed_arr![u16; 4096; { 0: 1, ^0: 2 }];
+//! assert_eq!(a[..2], [1, 0]);
+//! assert_eq!(a[4095], 2);
+//! ```
+//!
+//! The element type must implement the unsafe `Zeroable` trait. It is
+//! implemented for integers, floats, `bool`, `char` and arrays of them, and can
+//! be implemented for your own types if zero bytes are a valid value.
+//!
+//! Note that this is rarely faster than `[0; N]`: with optimizations, both
+//! compile to a single `memset`. In a benchmark creating a `[u32; 65536]`, both
+//! took the same time in release mode, and `zeroed_arr!` was about 60% slower
+//! in debug mode. Run `cargo bench --bench zeroed` to compare them yourself.
+//!
+//! ## Collecting iterators
+//!
+//! With `collect`, the array is filled with the first items of an iterator.
+//! If the iterator yields more items than the array can hold, the remaining
+//! items are ignored. If it yields too few, `arr!` panics, and `try_arr!`
+//! returns an error:
+//!
+//! ```
+//! # use array_lit::{arr, try_arr, TooFewItemsError};
+//! let a = arr![collect (1..).map(|i| i * 2); 4];
+//! assert_eq!(a, [2, 4, 6, 8]);
+//!
+//! let b = try_arr![collect "abc".chars(); 4];
+//! assert_eq!(b, Err(TooFewItemsError { yielded: 3, expected: 4 }));
+//! ```
+//!
+//! ## Arrays from slices
+//!
+//! `arr_from_slice!` converts a slice into an array. It returns an error if the
+//! slice has a different length:
+//!
+//! ```
+//! # use array_lit::{arr_from_slice, SliceLengthError};
+//! let v = [1, 2, 3, 4];
+//! assert_eq!(arr_from_slice![v[1..]; 3], Ok([2, 3, 4]));
+//! assert_eq!(arr_from_slice![v; 3], Err(SliceLengthError { len: 4, expected: 3 }));
+//! ```
+//!
+//! ## Ranges of integers
+//!
+//! `arr_range!` creates an array of consecutive integers. Without a start, it
+//! begins at `0_usize`:
+//!
+//! ```
+//! # use array_lit::arr_range;
+//! assert_eq!(arr_range![4], [0, 1, 2, 3]);
+//! assert_eq!(arr_range![3; 4], [3, 4, 5, 6]);
+//! let a: [u8; 3] = arr_range![1; 3];
+//! assert_eq!(a, [1, 2, 3]);
+//! ```
+//!
+//! ## Reading earlier elements
+//!
+//! If the value of an entry is written as a closure with one argument, the
+//! closure's body is evaluated with a shared reference to the array as it is
+//! at that point, i.e. after the entries before it have been applied. This
+//! makes it possible to compute an element from other elements:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![1; 6; { 2: |a| a[0] + a[1], 3: |a| a[1] + a[2], 5: |a| a[3] * 2 }];
+//! assert_eq!(a, [1, 1, 2, 3, 1, 6]);
+//! ```
+//!
+//! The value is computed once before it is assigned, so the reference can't be
+//! used afterwards. For a range or several indices, the value is cloned for
+//! each index.
+//!
+//! To use the array in every entry, it can be named once before the block with
+//! `as`. Then every value is evaluated like the body of such a closure:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![1; 6; as a { 2: a[0] + a[1], 3: a[1] + a[2], 5: a[3] * 2 }];
+//! assert_eq!(a, [1, 1, 2, 3, 1, 6]);
+//! ```
+//!
+//! Elements that haven't been assigned yet still contain the fill value. The name
+//! can't be used in `[i]: [a, b] * n`, `from` and `of` entries.
+//!
+//! A closure with two arguments, `|prev, i|`, is called with a reference to the
+//! previous element and the index, for every index of a range. This is useful
+//! for running totals and similar sequences. Index 0 has no previous element,
+//! so the range must start at 1 or later, and the first element must be set
+//! separately:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![0; 6; { 0: 1, 1..: |prev, i| prev + i }];
+//! assert_eq!(a, [1, 2, 4, 7, 11, 16]);
+//! ```
+//!
+//! ## Modifying elements
+//!
+//! Instead of overwriting an element, an entry can modify it with a compound
+//! assignment operator such as `+=`, `-=`, `*=` or `|=`. This is useful when
+//! the fill value is meaningful, and it can be combined with other entries:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![10; 6; { 3: += 5, 0: 1, 0: *= 3, 1..=2: -= 4, ^0: += |a| a[3] }];
+//! assert_eq!(a, [3, 6, 6, 15, 10, 25]);
+//! ```
+//!
+//! Like other entries, compound assignments are applied in order. They can't
+//! be used with `[i]: [a, b]`, stepped ranges or `from` entries.
+//!
+//! ## Setting fields of elements
+//!
+//! If the elements are tuples or structs, a single field can be set by writing
+//! it after the index, like `3 .1` or `0.x`. Compound assignments work as well.
+//! The index must be an integer literal or in parentheses, because `pos.0`
+//! is still the index `pos.0`. A tuple field after a literal index needs a space
+//! or parentheses, since `3.1` is a float literal:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![(0, 0); 4; { 3 .0: 5, (3).1: 9, 1 .1: += 2 }];
+//! assert_eq!(a, [(0, 0), (0, 2), (0, 0), (5, 9)]);
+//!
+//! #[derive(Debug, Clone, Copy, PartialEq)]
+//! struct Point { x: i32, y: i32 }
+//!
+//! let i = 1;
+//! let b = arr![Point { x: 0, y: 0 }; 2; { 0.x: 1, (i).y: 2 }];
+//! assert_eq!(b, [Point { x: 1, y: 0 }, Point { x: 0, y: 2 }]);
+//! ```
+//!
+//! ## Post-processing
+//!
+//! A closure can be given after the block with `then`. It is called with a
+//! mutable reference to every element, after all entries have been applied:
+//!
+//! ```
+//! # use array_lit::arr;
+//! let a = arr![0; 5; { 3: 1 } then |x| *x += 100];
+//! assert_eq!(a, [100, 100, 100, 101, 100]);
+//! ```
+//!
+//! ## Tracing assigned indices
+//!
+//! To check which elements a block assigns, for example in a generated table,
+//! use `arr_traced!`. It returns the array together with a `Vec` of every
+//! index that was assigned, in order. Ranges are expanded to all their indices:
+//!
+//! ```
+//! # #[cfg(feature = "std")] {
+//! # use array_lit::arr_traced;
+//! let (a, indices) = arr_traced![0; 8; { 6: 1, 1..=3: 2 }];
+//! assert_eq!(a, [0, 2, 2, 2, 0, 0, 1, 0]);
+//! assert_eq!(indices, [6, 1, 2, 3]);
+//! # }
+//! ```
+//!
+//! For quick debugging, `arr_dbg!` works like `arr!`, but also prints the
+//! array to stderr together with the file and line, like `std::dbg!`. The
+//! array is returned unchanged:
+//!
+//! ```
+//! # #[cfg(feature = "std")] {
+//! # use array_lit::arr_dbg;
+//! let a = arr_dbg![0; 4; { 3: 1 }];
+//! assert_eq!(a, [0, 0, 0, 1]);
+//! # }
+//! ```
+//!
+//! ## Concatenating arrays
+//!
+//! The `arr_concat!` macro joins several arrays of `Copy` elements. The length
+//! of the result is the sum of their lengths, so every array must either be a
+//! literal or a constant. It also works in `const` and `static` items:
+//!
+//! ```
+//! # use array_lit::arr_concat;
+//! const LOW: [u8; 2] = [0, 1];
+//! const TABLE: [u8; 6] = arr_concat![LOW, [2, 3], [9; 2]];
+//! assert_eq!(TABLE, [0, 1, 2, 3, 9, 9]);
+//! ```
+//!
+//! ## Handling invalid indices
+//!
+//! Indices that are out of bounds cause a panic. When the indices are only
+//! known at runtime, e.g. because they come from user input, you can use
+//! `try_arr!` or `try_vec!` instead, which return a `Result`:
+//!
+//! ```
+//! # use array_lit::{try_arr, IndexError};
+//! let index = 5;
+//! let a = try_arr![0; 4; { 0: 1, index: 2 }];
+//! assert_eq!(a, Err(IndexError { index: 5, len: 4 }));
+//! ```
+//!
+//! The `try_` macros only support `usize` indices.
+//!
+//! `try_vec!` can also compute every element with an expression that may fail.
+//! The `?` operator returns the first error:
+//!
+//! ```
+//! # #[cfg(feature = "std")] {
+//! # use array_lit::try_vec;
+//! let input = ["4", "2", "x"];
+//! let a: Result<Vec<u8>, std::num::ParseIntError> = try_vec![|i| input[i].parse()?; 2];
+//! assert_eq!(a, Ok(vec![4, 2]));
+//! # }
+//! ```
+//!
+//! ## Two-dimensional arrays
+//!
+//! The `arr2d!` macro creates an array of arrays. The dimensions are given as
+//! `rows, columns`, and cells are indexed with a `(row, column)` tuple. A
+//! whole row can be set with a `(row,)` index:
+//!
+//! ```
+//! # use array_lit::arr2d;
+//! let a = arr2d![0; 3, 4; { (1, 2): 9, (2,): [1, 2, 3, 4] }];
+//! assert_eq!(a, [[0, 0, 0, 0], [0, 0, 9, 0], [1, 2, 3, 4]]);
+//! ```
+//!
+//! A whole column is set with `col`, followed by the column and one value per
+//! row:
+//!
+//! ```
+//! # use array_lit::arr2d;
+//! let a = arr2d![0; 3, 2; { col 1: [1, 2, 3] }];
+//! assert_eq!(a, [[0, 1], [0, 2], [0, 3]]);
+//! ```
+//!
+//! The `arr2d_flat!` macro fills a two-dimensional array row by row from a flat
+//! list instead. The number of values must be `rows * columns`:
+//!
+//! ```
+//! # use array_lit::arr2d_flat;
+//! let a = arr2d_flat![0; 2, 3; [1, 2, 3, 4, 5, 6]];
+//! assert_eq!(a, [[1, 2, 3], [4, 5, 6]]);
+//! ```
+//!
+//! To write the data row by row but store it column by column, use
+//! `arr_transpose!`. The dimensions are those of the rows, so the result
+//! below is a `[[i32; 2]; 3]`:
+//!
+//! ```
+//! # use array_lit::arr_transpose;
+//! let a = arr_transpose![0; 2, 3; rows [[1, 2, 3], [4, 5, 6]]];
+//! assert_eq!(a, [[1, 4], [2, 5], [3, 6]]);
+//! ```
+//!
+//! For volumetric data, `arr3d!` creates an array of arrays of arrays. The
+//! dimensions are given as `x, y, z`, and a cell with the index `(x, y, z)` is
+//! `arr[x][y][z]`:
+//!
+//! ```
+//! # use array_lit::arr3d;
+//! let a = arr3d![0; 2, 2, 2; { (1, 0, 1): 9 }];
+//! assert_eq!(a[1][0][1], 9);
+//! assert_eq!(a[0], [[0, 0], [0, 0]]);
+//! ```
+//!
+//! ## Building arrays at runtime
+//!
+//! When the entries are computed by a loop or come from another data
+//! structure, the [`ArrayBuilder`](builder/struct.ArrayBuilder.html) type can
+//! be used instead of a macro. Its methods are applied in order, just like the
+//! entries of `arr!`:
+//!
+//! ```
+//! # use array_lit::arr;
+//! use array_lit::builder::ArrayBuilder;
+//!
+//! let mut builder = ArrayBuilder::new(0).set_range(4.., 1);
+//! for i in [1, 2] {
+//!     builder = builder.set(i, i * 10);
+//! }
+//! let a: [usize; 6] = builder.build();
+//! assert_eq!(a, arr![0; 6; { 1: 10, 2: 20, 4..: 1 }]);
+//! ```
+//!
+//! For `Copy` elements, the functions [`fill_range`](fn.fill_range.html) and
+//! [`fill_from_slice`](fn.fill_from_slice.html) fill a part of any slice. They
+//! panic if the range is out of bounds:
+//!
+//! ```
+//! let mut a = [0; 6];
+//! array_lit::fill_range(&mut a, 1..3, 9);
+//! array_lit::fill_from_slice(&mut a, 4, &[1, 2]);
+//! assert_eq!(a, [0, 9, 9, 0, 1, 2]);
+//! ```
+//!
+//! [`repeat_pattern`](fn.repeat_pattern.html) fills a slice with a repeating
+//! pattern, like the `repeat` entry, and also works with `Clone` elements:
+//!
+//! ```
+//! let mut a = vec![String::new(); 3];
+//! array_lit::repeat_pattern(&mut a, &["a".to_string(), "b".to_string()]);
+//! assert_eq!(a, ["a", "b", "a"]);
+//! ```
+//!
+//! When only a few elements of a long or infinite sequence are read, the
+//! [`OverrideIter`](iter/struct.OverrideIter.html) type applies overrides to
+//! an iterator lazily. The overrides must be sorted by index:
+//!
+//! ```
+//! use array_lit::iter::OverrideIter;
+//! use std::iter::repeat;
+//!
+//! let iter = OverrideIter::new(repeat(0), [(1, 5), (3, 7)]);
+//! assert_eq!(iter.take(5).collect::<Vec<_>>(), [0, 5, 0, 7, 0]);
+//! ```
+//!
+//! ## What about array lifetimes?
+//!
+//! In trivial cases such as `arr![3; 5]`, the `'static` lifetime is inferred
+//! for array literals. This means that they have the exact same behavior as
+//! normal array literals.
+//!
+//! In the other cases, the `'static` lifetime is not inferred. This means that
+//! the array literal is computed at runtime and doesn't have a fixed memory
+//! location. It also means that the following
+//!
+//! ```compile_fail
+//! # use array_lit::arr;
+//! fn return_temporary() -> &'static [i32; 4] {
+//!     &arr![0; 4; { 0: 1 }]
+//! }
+//! ```
+//!
+//! produces `error[E0515]: cannot return reference to temporary value`. This
+//! can be solved by assigning the literal to a `const` or `static` variable
+//! first:
+//!
+//! ```
+//! # use array_lit::arr;
+//! fn return_temporary() -> &'static [i32; 4] {
+//!     static ARR: &[i32; 4] = &arr![0; 4; { 0: 1 }];
+//!     ARR
+//! }
+//! ```
+//!
+//! Values assigned to a `static` or `const` variable must be constant. Entries
+//! whose indices and range bounds are literals are plain assignments, so they
+//! can be used there, also in a block that spans several lines:
+//!
+//! ```
+//! # use array_lit::arr;
+//! const ARR: [i32; 8] = arr![0; 8; {
+//!     0: 1, // the first element
+//!     2: 3,
+//!     7: 9,
+//! }];
+//! assert_eq!(ARR, [1, 0, 3, 0, 0, 0, 0, 9]);
+//! ```
+//!
+//! However, the `arr!` macro copies slices with
+//! `copy_from_slice`, which can't be called there:
+//!
+//! ```compile_fail
+//! # use array_lit::arr;
+//! const HEADER: [i32; 2] = [1, 2];
+//! const ARR: [i32; 4] = arr![0; 4; { [0]: HEADER }];
+//! ```
+//!
+//! Use the `const_arr!` macro instead, which accepts the same syntax as `arr!`,
+//! but copies slices element by element:
+//!
+//! ```
+//! # use array_lit::const_arr;
+//! const HEADER: [i32; 2] = [1, 2];
+//! const ARR: [i32; 4] = const_arr![0; 4; { [0]: HEADER, [2]: [3; 2] }];
+//! assert_eq!(ARR, [1, 2, 3, 3]);
+//! ```
+//!
+//! Entries like `[2]: [3; 2]` are filled by a `const fn`, so their length
+//! doesn't have to be a constant. This allows using them in a `const fn`:
+//!
+//! ```
+//! # use array_lit::const_arr;
+//! const fn ones_at_end<const N: usize>(len: usize) -> [i32; N] {
+//!     const_arr![0; N; { [(N - len)]: [1; len] }]
+//! }
+//! const ARR: [i32; 4] = ones_at_end(3);
+//! assert_eq!(ARR, [0, 1, 1, 1]);
+//! ```
+//!
+//! Most other entries work as well, including `as a`, `mirror`, `range`,
+//! compound assignments, `in` with an array of indices and sub-slices with
+//! `of`. Entries that call a closure or iterate over an iterator can't be
+//! evaluated at compile time, so `fn`, `where`, `if`, `from_pairs` and groups
+//! aren't supported:
+//!
+//! ```
+//! # use array_lit::const_arr;
+//! const HEADER: [u8; 4] = [1, 2, 3, 4];
+//! const A: [u8; 8] = const_arr![0; 8; as a {
+//!     [0]: HEADER[1..3] of 2,
+//!     in [2, 5]: 7,
+//!     5: += a[0],
+//!     ^0: 9,
+//! }];
+//! assert_eq!(A, [2, 3, 7, 0, 0, 9, 0, 9]);
+//! ```
+//!
+//! Note that `const` enforces **const evaluation**, which means that the whole
+//! array is included in the application binary. This might not be desirable if
+//! the array is large.
+//!
+//! ## Usage
+//!
+//! Import the macros with
+//!
+//! ```ignore
+//! use array_lit::{arr, vec};
+//! ```
+//!
+//! If you don't want to shadow `std::vec!`, you can rename the macro:
+//!
+//! ```ignore
+//! use array_lit::{arr, vec as vector};
+//! ```
+//!
+//! Importing the macros globally is also supported, although not recommended:
+//!
+//! ```ignore
+//! #[macro_use]
+//! extern crate array_lit;
+//! ```
+//!
+//! ## Custom indices
+//!
+//! If you want to use your own `Index`/`IndexMut` implementation in these
+//! macros, you probably need an extra pair of parentheses:
+//!
+//! ```ignore
+//! #[derive(Copy, Clone)]
+//! struct S(bool);
+//!
+//! /// Your custom index
+//! struct Idx(usize);
 //!
 //! impl std::ops::Index<Idx> for Vec<S> {
 //!     type Output = bool;
@@ -172,103 +1085,4565 @@
 //! // parens needed ~~~~~^~~~~~~~^
 //! ```
 //!
-//! ## `no_std` support
+//! ## Spreading arrays into a `Vec`
 //!
-//! This library supports `no_std`, if default features are disabled.
-//! This makes the `vec!` macro unavailable.
+//! The comma-separated form of `vec!` can splice in the elements of another
+//! array, slice or `Vec` with `..`. The elements are cloned in order:
 //!
-//! ## Minimum required Rust version
+//! ```
+//! # #[cfg(feature = "std")] {
+//! # use array_lit::vec;
+//! let other = [3, 4];
+//! assert_eq!(vec![1, 2, ..other, 9], std::vec![1, 2, 3, 4, 9]);
+//! # }
+//! ```
+//!
+//! This is not supported by `arr!`, since the length of an array must be
+//! known at compile time.
+//!
+//! ## Reserving capacity
+//!
+//! The `vec!` macro can reserve more capacity than the length, so elements can
+//! be pushed later without reallocating. The capacity is written after the
+//! length with `cap`:
+//!
+//! ```
+//! # #[cfg(feature = "std")] {
+//! # use array_lit::vec;
+//! let a = vec![0; 8 cap 64; { 3: 1 }];
+//! assert_eq!(a.len(), 8);
+//! assert!(a.capacity() >= 64);
+//! # }
+//! ```
+//!
+//! The length and the capacity must be single tokens, so more complex
+//! expressions need parentheses.
+//!
+//! ## Inferring the length of a `Vec`
+//!
+//! Unlike `_`, which only works with literal indices, `auto` infers the
+//! length of a `Vec` at runtime. The `Vec` is just long enough to contain the
+//! end of every entry, so `[0]: src` produces a `Vec` with the length of `src`:
+//!
+//! ```
+//! # #[cfg(feature = "std")] {
+//! # use array_lit::vec;
+//! let src = [1, 2, 3];
+//! let (a, b) = ([4, 5], [6]);
+//! assert_eq!(vec![0; auto; { [0]: src }], [1, 2, 3]);
+//! assert_eq!(vec![0; auto; { [1]: a, [4]: b }], [0, 4, 5, 0, 6]);
+//! # }
+//! ```
+//!
+//! Entries are assigned in order, so overlapping entries overwrite each other
+//! like in other `vec!` literals. Entries without an end, such as `^0` or
+//! `5..`, can't be used with `auto`.
+//!
+//! ## Modifying a copy
+//!
+//! To apply entries to a copy of an existing `Vec`, array or slice, write `from`
+//! and the original instead of the fill value and length. The elements are
+//! cloned, and the original stays unchanged:
+//!
+//! ```
+//! # #[cfg(feature = "std")] {
+//! # use array_lit::vec;
+//! let base = vec![1, 2, 3, 4];
+//! let copy = vec![from base; { 0: 0, 2..: 9 }];
+//! assert_eq!(base, [1, 2, 3, 4]);
+//! assert_eq!(copy, [0, 2, 9, 9]);
+//! # }
+//! ```
+//!
+//! The copy has the same length as the original, so indices past its end panic.
 //!
-//! Requires Rust 1.33.
+//! ## Removing elements
+//!
+//! `remove` and an index removes an element from a `Vec`, which makes it one
+//! element shorter. Removals must come after all other entries in the block,
+//! and are applied after them. Every index refers to the position before any
+//! element was removed, so the elements are removed starting with the highest
+//! index, and an index that is given twice is only removed once:
+//!
+//! ```
+//! # #[cfg(feature = "std")] {
+//! # use array_lit::vec;
+//! let a = vec![0; 6; { [0]: [1, 2, 3, 4], remove 1, remove 3 }];
+//! assert_eq!(a, [1, 3, 0, 0]);
+//! # }
+//! ```
+//!
+//! ## Boxed slices
+//!
+//! Large arrays can overflow the stack. The `boxed_arr!` macro accepts the
+//! same syntax as `vec!`, but returns a `Box<[T]>`:
+//!
+//! ```
+//! # #[cfg(feature = "std")] {
+//! # use array_lit::boxed_arr;
+//! let a: Box<[i32]> = boxed_arr![0; 1_000_000; { 5: 1 }];
+//! assert_eq!(a[5], 1);
+//! # }
+//! ```
+//!
+//! ## Shared slices
+//!
+//! The `arc_arr!` and `rc_arr!` macros also accept the same syntax as `vec!`,
+//! but return an `Arc<[T]>` or an `Rc<[T]>`, which is handy for tables that
+//! are shared, but never modified:
+//!
+//! ```
+//! # #[cfg(feature = "std")] {
+//! # use array_lit::{arc_arr, rc_arr};
+//! use std::{rc::Rc, sync::Arc};
+//!
+//! let a: Arc<[i32]> = arc_arr![0; 8; { 3: 1 }];
+//! let b: Rc<[i32]> = rc_arr![1, 2, 3];
+//! assert_eq!(*a, [0, 0, 0, 1, 0, 0, 0, 0]);
+//! assert_eq!(*b, [1, 2, 3]);
+//! # }
+//! ```
+//!
+//! ## `VecDeque`
+//!
+//! The `vec_deque!` macro accepts the same syntax as `vec!`, but returns a
+//! `VecDeque`:
+//!
+//! ```
+//! # #[cfg(feature = "std")] {
+//! # use array_lit::vec_deque;
+//! let a = vec_deque![0; 6; { 3: 1, [4]: [2, 3] }];
+//! assert_eq!(a, [0, 0, 0, 1, 2, 3]);
+//! # }
+//! ```
+//!
+//! ## Strings
+//!
+//! The `string!` macro accepts the same syntax as `vec!`, but with `char`
+//! elements, and joins them into a `String`. Indices count characters, not
+//! bytes:
+//!
+//! ```
+//! # #[cfg(feature = "std")] {
+//! # use array_lit::string;
+//! let a = string!['-'; 8; { 0: '[', 3..5: '═', ^0: ']' }];
+//! assert_eq!(a, "[--══--]");
+//! # }
+//! ```
+//!
+//! ## `HashMap`
+//!
+//! The `hash_map!` macro creates a `HashMap` with the same `key: value` syntax.
+//! If a key appears more than once, the last value wins:
+//!
+//! ```
+//! # #[cfg(feature = "std")] {
+//! # use array_lit::hash_map;
+//! let a = hash_map! { "a": 1, "b": 2, "a": 3 };
+//! assert_eq!(a["a"], 3);
+//! # }
+//! ```
+//!
+//! The `btree_map!` macro works the same way, but creates a `BTreeMap`, which
+//! is also available with the `alloc` feature.
+//!
+//! ## `SmallVec`
+//!
+//! With the `smallvec` feature, the `small_vec!` macro creates a `SmallVec`
+//! from the [`smallvec`](https://docs.rs/smallvec) crate. It accepts the same
+//! syntax as `vec!`. The inline capacity is inferred from the type:
+//!
+//! ```
+//! # #[cfg(feature = "smallvec")] {
+//! # use array_lit::small_vec;
+//! use smallvec::SmallVec;
+//!
+//! let a: SmallVec<[i32; 8]> = small_vec![0; 4; { 1: 1, ^0: 3 }];
+//! assert_eq!(&a[..], [0, 1, 0, 3]);
+//! # }
+//! ```
+//!
+//! The feature adds a dependency on `smallvec`, which the macro uses
+//! internally. To name the `SmallVec` type, your crate still needs to depend on
+//! `smallvec` itself.
+//!
+//! ## Serde
+//!
+//! With the `serde` feature, arrays can be read from configuration files in the
+//! same sparse form: a default value, and the elements that differ from it.
+//! The [`sparse::SparseArray`](sparse/struct.SparseArray.html) type
+//! describes this form, and the `sparse::deserialize` and `sparse::serialize`
+//! functions can be used for array fields:
+//!
+//! ```
+//! # #[cfg(feature = "serde")] {
+//! # use serde::Deserialize;
+//! #[derive(Deserialize)]
+//! struct Config {
+//!     #[serde(deserialize_with = "array_lit::sparse::deserialize")]
+//!     table: [u8; 6],
+//! }
+//!
+//! let json = r#"{ "table": { "default": 0, "overrides": { "3": 1 } } }"#;
+//! let config: Config = serde_json::from_str(json).unwrap();
+//! assert_eq!(config.table, [0, 0, 0, 1, 0, 0]);
+//! # }
+//! ```
+//!
+//! An override that is out of bounds is an error. The `serde` feature also
+//! enables the `alloc` feature.
+//!
+//! ## `no_std` support
+//!
+//! This library supports `no_std`, if default features are disabled.
+//! This makes the `vec!`, `boxed_arr!`, `arc_arr!`, `rc_arr!`, `arr_traced!`,
+//! `vec_deque!`, `string!` and `btree_map!` macros unavailable, unless the
+//! `alloc` feature is enabled:
+//!
+//! ```toml
+//! [dependencies]
+//! array-lit = { version = "0.2", default-features = false, features = ["alloc"] }
+//! ```
+//!
+//! Then the macros use the `alloc` crate instead of `std`.
+//!
+//! ## Minimum required Rust version
+//!
+//! Requires Rust 1.63.
+//!
+//! `const_arr!` relies on loops and trait bounds in `const fn`s, which are
+//! available since Rust 1.61, so it works with this version as well.
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[doc(hidden)]
+pub extern crate alloc;
+
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub use std::collections::HashMap as __HashMap;
+
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub use std::eprintln as __eprintln;
+
+#[cfg(feature = "smallvec")]
+#[doc(hidden)]
+pub use smallvec as __smallvec;
+
+pub mod builder;
+
+pub mod iter;
+
+#[cfg(feature = "serde")]
+pub mod sparse;
+
+#[cfg(test)]
+mod tests;
+
+/// The error returned by [`try_arr!`](macro.try_arr.html) and
+/// [`try_vec!`](macro.try_vec.html) when an index is out of bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexError {
+    /// The index that was out of bounds
+    pub index: usize,
+    /// The length of the array or `Vec`
+    pub len: usize,
+}
+
+impl core::fmt::Display for IndexError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "index {} is out of bounds for length {}",
+            self.index, self.len,
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IndexError {}
+
+/// The error returned by [`try_arr!`](macro.try_arr.html) when an iterator
+/// is collected into an array, but yields too few items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TooFewItemsError {
+    /// The number of items the iterator yielded
+    pub yielded: usize,
+    /// The length of the array
+    pub expected: usize,
+}
+
+impl core::fmt::Display for TooFewItemsError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "the iterator yielded {} items, but {} were expected",
+            self.yielded, self.expected,
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TooFewItemsError {}
+
+/// The error returned by [`arr_from_slice!`](macro.arr_from_slice.html) when
+/// the slice doesn't have the length of the array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SliceLengthError {
+    /// The length of the slice
+    pub len: usize,
+    /// The length of the array
+    pub expected: usize,
+}
+
+impl core::fmt::Display for SliceLengthError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "the slice has length {}, but {} was expected",
+            self.len, self.expected,
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SliceLengthError {}
+
+/// Sets every element of `arr` in `range` to `value`.
+///
+/// This is what a range entry such as `{ 2..5: 0 }` does, except that the
+/// value is copied instead of evaluated again for every element.
+///
+/// # Panics
+///
+/// Panics if `range.start > range.end`, or if `range.end > arr.len()`.
+///
+/// # Example
+///
+/// ```
+/// let mut a = [0; 6];
+/// array_lit::fill_range(&mut a, 2..5, 7);
+/// assert_eq!(a, [0, 0, 7, 7, 7, 0]);
+/// ```
+pub fn fill_range<T: Copy>(arr: &mut [T], range: core::ops::Range<usize>, value: T) {
+    arr[range].fill(value);
+}
+
+/// Copies `src` into `arr`, starting at the index `start`.
+///
+/// This is what an entry such as `{ [start]: slice }` does with arrays and
+/// `Vec`s.
+///
+/// # Panics
+///
+/// Panics if `start + src.len() > arr.len()`.
+///
+/// # Example
+///
+/// ```
+/// let mut a = [0; 6];
+/// array_lit::fill_from_slice(&mut a, 3, &[1, 2]);
+/// assert_eq!(a, [0, 0, 0, 1, 2, 0]);
+/// ```
+pub fn fill_from_slice<T: Copy>(arr: &mut [T], start: usize, src: &[T]) {
+    arr[start..start + src.len()].copy_from_slice(src);
+}
+
+/// Fills `dst` with copies of `pattern`, repeated from the start. If
+/// `pattern` doesn't fit evenly, the last repetition is cut off.
+///
+/// This is what a `repeat` entry such as `{ repeat: [1, 2] }` does. If
+/// `pattern` is empty, `dst` is left unchanged.
+///
+/// # Example
+///
+/// ```
+/// let mut a = [0; 5];
+/// array_lit::repeat_pattern(&mut a, &[1, 2]);
+/// assert_eq!(a, [1, 2, 1, 2, 1]);
+/// ```
+pub fn repeat_pattern<T: Clone>(dst: &mut [T], pattern: &[T]) {
+    for (dst, src) in dst.iter_mut().zip(pattern.iter().cycle()) {
+        dst.clone_from(src);
+    }
+}
+
+/// Wraps an array or `Vec` for the `try_` macros. Writing to an index that is
+/// out of bounds stores an `IndexError` and writes to `scratch` instead.
+///
+/// Without a scratch value, the first element is written instead, since the
+/// array is discarded anyway. Only an empty array without a scratch value
+/// still panics.
+#[doc(hidden)]
+pub struct __TryArr<A, T> {
+    pub arr: A,
+    pub scratch: Option<T>,
+    pub error: Option<IndexError>,
+}
+
+impl<A: AsRef<[T]>, T> __TryArr<A, T> {
+    pub fn new(arr: A, scratch: T) -> Self {
+        __TryArr {
+            arr,
+            scratch: Some(scratch),
+            error: None,
+        }
+    }
+
+    pub fn without_scratch(arr: A) -> Self {
+        __TryArr {
+            arr,
+            scratch: None,
+            error: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.arr.as_ref().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.arr.as_ref().is_empty()
+    }
+
+    /// Like `__IndexFromEnd::index_from_end`, but stores an `IndexError`
+    /// with the offset and returns an index past the end if it is out of
+    /// bounds.
+    pub fn index_from_end(&mut self, len: usize, offset: usize) -> usize {
+        if offset < len {
+            len - 1 - offset
+        } else {
+            self.error.get_or_insert(IndexError { index: offset, len });
+            len
+        }
+    }
+
+    pub fn finish(self) -> Result<A, IndexError> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.arr),
+        }
+    }
+}
+
+impl<A: AsRef<[T]>, T> core::ops::Index<usize> for __TryArr<A, T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        let arr = self.arr.as_ref();
+        match (arr.get(index), &self.scratch, arr.first()) {
+            (Some(item), _, _) => item,
+            (None, Some(scratch), _) => scratch,
+            (None, None, Some(first)) => first,
+            (None, None, None) => panic!("array-lit: index {} out of bounds for len 0", index),
+        }
+    }
+}
+
+impl<A: AsRef<[T]> + AsMut<[T]>, T> core::ops::IndexMut<usize> for __TryArr<A, T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let len = self.len();
+        if index < len {
+            return &mut self.arr.as_mut()[index];
+        }
+        self.error.get_or_insert(IndexError { index, len });
+        match (&mut self.scratch, self.arr.as_mut().first_mut()) {
+            (Some(scratch), _) => scratch,
+            (None, Some(first)) => first,
+            (None, None) => panic!("array-lit: index {} out of bounds for len 0", index),
+        }
+    }
+}
+
+/// Converts an index from the end into an index from the start, and panics
+/// if it is out of bounds. `__TryArr` has an inherent method with the same
+/// name, which is preferred.
+#[doc(hidden)]
+pub trait __IndexFromEnd {
+    #[track_caller]
+    fn index_from_end(&mut self, len: usize, offset: usize) -> usize {
+        assert!(
+            offset < len,
+            "array-lit: index ^{} is out of bounds for length {}",
+            offset,
+            len,
+        );
+        len - 1 - offset
+    }
+}
+
+impl<A: ?Sized> __IndexFromEnd for A {}
+
+#[doc(hidden)]
+pub const fn __has_overlap(ranges: &[(usize, usize)]) -> bool {
+    let mut i = 0;
+    while i < ranges.len() {
+        let mut j = i + 1;
+        while j < ranges.len() {
+            let (a, b) = (ranges[i], ranges[j]);
+            if a.0 < a.1 && b.0 < b.1 && a.0 < b.1 && b.0 < a.1 {
+                return true;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    false
+}
+
+#[doc(hidden)]
+pub const fn __has_duplicate(indices: &[usize]) -> bool {
+    let mut i = 0;
+    while i < indices.len() {
+        let mut j = i + 1;
+        while j < indices.len() {
+            if indices[i] == indices[j] {
+                return true;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    false
+}
+
+#[doc(hidden)]
+pub const fn __max_end(ranges: &[(usize, usize)]) -> usize {
+    let mut max = 0;
+    let mut i = 0;
+    while i < ranges.len() {
+        if ranges[i].1 > max {
+            max = ranges[i].1;
+        }
+        i += 1;
+    }
+    max
+}
+
+#[doc(hidden)]
+pub const fn __covers_all(indices: &[usize], len: usize) -> bool {
+    if indices.len() != len {
+        return false;
+    }
+    let mut i = 0;
+    while i < len {
+        let mut found = false;
+        let mut j = 0;
+        while j < indices.len() {
+            if indices[j] == i {
+                found = true;
+            }
+            j += 1;
+        }
+        if !found {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Types for which a value with all bytes set to zero is valid, so they can be
+/// used with [`zeroed_arr!`](macro.zeroed_arr.html).
+///
+/// This is implemented for all integer and floating-point types, `bool`,
+/// `char`, and arrays of such types.
+///
+/// # Safety
+///
+/// Implementing this trait is only sound if the all-zero bit pattern is a
+/// valid value of the type. This is not the case for references, `Box`,
+/// function pointers or `NonZero*` integers, for example.
+pub unsafe trait Zeroable {}
+
+macro_rules! impl_zeroable {
+    ($($ty:ty),*) => {
+        $( unsafe impl Zeroable for $ty {} )*
+    };
+}
+
+impl_zeroable!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+impl_zeroable!(f32, f64, bool, char);
+
+unsafe impl<T: Zeroable, const N: usize> Zeroable for [T; N] {}
+
+/// Calls `f` with every index below `len` and collects the elements into a
+/// `Vec`, or returns the first error.
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[doc(hidden)]
+pub fn __try_collect<T, E>(
+    len: usize,
+    mut f: impl FnMut(usize) -> Result<T, E>,
+) -> Result<alloc::vec::Vec<T>, E> {
+    let mut vec = alloc::vec::Vec::with_capacity(len);
+    for i in 0..len {
+        vec.push(f(i)?);
+    }
+    Ok(vec)
+}
+
+/// Removes the elements at `indices` from `vec`, starting with the highest
+/// index, so every index refers to the position before any element was
+/// removed. An index that is given more than once is only removed once.
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[doc(hidden)]
+pub fn __remove_indices<T, const N: usize>(vec: &mut alloc::vec::Vec<T>, mut indices: [usize; N]) {
+    indices.sort_unstable();
+    let len = vec.len();
+    let mut previous = None;
+    for &index in indices.iter().rev() {
+        if previous == Some(index) {
+            continue;
+        }
+        assert!(
+            index < len,
+            "array-lit: index {} out of bounds for len {}",
+            index,
+            len,
+        );
+        vec.remove(index);
+        previous = Some(index);
+    }
+}
+
+/// Clones `value` into all elements except the last one, and moves it into
+/// the last element.
+#[doc(hidden)]
+pub fn __clone_array<T: Clone, const N: usize>(value: T) -> [T; N] {
+    let mut value = Some(value);
+    core::array::from_fn(|i| {
+        if i + 1 == N {
+            value.take().unwrap()
+        } else {
+            value.as_ref().unwrap().clone()
+        }
+    })
+}
+
+#[doc(hidden)]
+pub fn __zeroed<T: Zeroable, const N: usize>() -> [T; N] {
+    // SAFETY: `T: Zeroable` guarantees that zero bytes are a valid `T`, and
+    // an array has no bytes besides its elements
+    unsafe { core::mem::MaybeUninit::zeroed().assume_init() }
+}
+
+#[doc(hidden)]
+pub fn __uninit_array<T, const N: usize>() -> [core::mem::MaybeUninit<T>; N] {
+    // SAFETY: `MaybeUninit<T>` doesn't require initialization, and neither
+    // does an array of them
+    unsafe { core::mem::MaybeUninit::uninit().assume_init() }
+}
+
+/// # Safety
+///
+/// All elements of `arr` must be initialized.
+#[doc(hidden)]
+pub unsafe fn __assume_init<T, const N: usize>(arr: [core::mem::MaybeUninit<T>; N]) -> [T; N] {
+    // `[MaybeUninit<T>; N]` has the same layout as `[T; N]`. `arr` is not
+    // dropped afterwards, because `MaybeUninit` never drops its contents.
+    (&arr as *const [core::mem::MaybeUninit<T>; N] as *const [T; N]).read()
+}
+
+#[doc(hidden)]
+pub union __Concat<T: Copy, const N: usize> {
+    uninit: [core::mem::MaybeUninit<T>; N],
+    init: [T; N],
+}
+
+/// Concatenates `parts` into an array of length `N`. This is a `const fn`,
+/// so it copies the elements one by one instead of using `copy_from_slice`.
+#[doc(hidden)]
+pub const fn __concat<T: Copy, const N: usize>(parts: &[&[T]]) -> [T; N] {
+    let mut arr = [core::mem::MaybeUninit::<T>::uninit(); N];
+    let mut len = 0;
+    let mut i = 0;
+    while i < parts.len() {
+        let mut j = 0;
+        while j < parts[i].len() {
+            assert!(len < N, "array-lit: the arrays are longer than expected");
+            arr[len] = core::mem::MaybeUninit::new(parts[i][j]);
+            len += 1;
+            j += 1;
+        }
+        i += 1;
+    }
+    assert!(len == N, "array-lit: the arrays are shorter than expected");
+    // SAFETY: all `N` elements were initialized in the loop above
+    unsafe { __Concat { uninit: arr }.init }
+}
+
+/// Returns whether the entry at position `entry` sets the element at
+/// `index`, if the entries are applied in the order of their start index.
+/// Entries with the same start index are applied in source order.
+#[doc(hidden)]
+pub const fn __is_last_write(ranges: &[(usize, usize)], entry: usize, index: usize) -> bool {
+    let start = ranges[entry].0;
+    let mut j = 0;
+    while j < ranges.len() {
+        let (s, e) = ranges[j];
+        let later = s > start || (s == start && j > entry);
+        if later && s <= index && index < e {
+            return false;
+        }
+        j += 1;
+    }
+    true
+}
+
+/// Calls `set` with every index from `start` to `end`. With the
+/// `outline-loops` feature, ranges are filled with this function instead of a
+/// loop in the generated code. It isn't generic, so it is only compiled once.
+#[doc(hidden)]
+pub fn __fill_range(start: usize, end: usize, step: usize, set: &mut dyn FnMut(usize)) {
+    let mut i = start;
+    while i < end {
+        set(i);
+        i += step;
+    }
+}
+
+/// Like `__fill_range`, but includes `end`.
+#[doc(hidden)]
+pub fn __fill_range_inclusive(start: usize, end: usize, step: usize, set: &mut dyn FnMut(usize)) {
+    let mut i = start;
+    while i <= end {
+        set(i);
+        i += step;
+    }
+}
+
+/// Adds two parts of an index with the `checked-indices` feature.
+#[doc(hidden)]
+pub const fn __checked_add(a: usize, b: usize) -> usize {
+    match a.checked_add(b) {
+        Some(sum) => sum,
+        None => panic!("array-lit: index overflow"),
+    }
+}
+
+/// Subtracts two parts of an index with the `checked-indices` feature.
+#[doc(hidden)]
+pub const fn __checked_sub(a: usize, b: usize) -> usize {
+    match a.checked_sub(b) {
+        Some(difference) => difference,
+        None => panic!("array-lit: index overflow"),
+    }
+}
+
+/// Multiplies two parts of an index with the `checked-indices` feature.
+#[doc(hidden)]
+pub const fn __checked_mul(a: usize, b: usize) -> usize {
+    match a.checked_mul(b) {
+        Some(product) => product,
+        None => panic!("array-lit: index overflow"),
+    }
+}
+
+/// Returns the array unchanged. Calling it with an explicit `T` sets the
+/// element type of an array with a trailing `: T`.
+#[doc(hidden)]
+pub const fn __typed<T, const N: usize>(arr: [T; N]) -> [T; N] {
+    arr
+}
+
+/// Returns the closure of a `fn` entry, so the type of its argument is known
+/// before it is called.
+#[doc(hidden)]
+pub fn __index_fn<T, F: FnMut(usize) -> T>(f: F) -> F {
+    f
+}
+
+/// Returns the guard of a `where` entry, so the type of its argument is known
+/// before it is called.
+#[doc(hidden)]
+pub fn __index_guard<F: FnMut(usize) -> bool>(f: F) -> F {
+    f
+}
+
+/// Returns the guard of an `if` entry. The array is only used to infer the
+/// type of the elements the guard is called with.
+#[doc(hidden)]
+pub fn __element_guard<T, A, F>(_arr: &A, f: F) -> F
+where
+    A: core::ops::Index<usize, Output = T> + ?Sized,
+    F: FnMut(&T) -> bool,
+{
+    f
+}
+
+/// Sets `len` elements starting at `start` to `value`. `const_arr!` uses this
+/// for `[start]: [value; len]` entries, so the loop isn't part of the
+/// generated code.
+#[doc(hidden)]
+pub const fn __fill_const<T: Copy, const N: usize>(
+    mut arr: [T; N],
+    start: usize,
+    len: usize,
+    value: T,
+) -> [T; N] {
+    let mut i = start;
+    while i < start + len {
+        arr[i] = value;
+        i += 1;
+    }
+    arr
+}
+
+#[doc(hidden)]
+pub struct __Bounds<'a, D: ?Sized, I>(pub &'a D, pub &'a I);
+
+/// Panics with a clearer message than the indexing expression if a `usize`
+/// index is out of bounds.
+#[doc(hidden)]
+pub trait __CheckIndex<T> {
+    fn check_index(&self);
+}
+
+impl<T, D: AsRef<[T]> + ?Sized> __CheckIndex<T> for __Bounds<'_, D, usize> {
+    #[track_caller]
+    fn check_index(&self) {
+        let len = self.0.as_ref().len();
+        let index = *self.1;
+        assert!(
+            index < len,
+            "array-lit: index {} out of bounds for len {}",
+            index,
+            len,
+        );
+    }
+}
+
+/// Fallback for other index types, and for collections that can't be
+/// borrowed as a slice, which aren't checked.
+#[doc(hidden)]
+pub trait __SkipCheckIndex {
+    fn check_index(&self);
+}
+
+impl<D: ?Sized, I> __SkipCheckIndex for &__Bounds<'_, D, I> {
+    fn check_index(&self) {}
+}
+
+#[doc(hidden)]
+pub struct __Dest<'a, D: ?Sized>(pub &'a mut D);
+
+/// Copies a slice into an array or `Vec` with `copy_from_slice`.
+#[doc(hidden)]
+pub trait __CopyFromSlice<T> {
+    fn copy_from<S: AsRef<[T]> + ?Sized>(&mut self, start: usize, src: &S);
+}
+
+impl<T: Copy, D: AsMut<[T]> + ?Sized> __CopyFromSlice<T> for __Dest<'_, D> {
+    fn copy_from<S: AsRef<[T]> + ?Sized>(&mut self, start: usize, src: &S) {
+        crate::fill_from_slice(self.0.as_mut(), start, src.as_ref());
+    }
+}
+
+/// Fallback for collections that can't be borrowed as a slice, which copies
+/// the elements one by one.
+#[doc(hidden)]
+pub trait __CopyByIndex<T> {
+    fn copy_from<S: AsRef<[T]> + ?Sized>(&mut self, start: usize, src: &S);
+}
+
+impl<T: Copy, D: core::ops::IndexMut<usize, Output = T> + ?Sized> __CopyByIndex<T>
+    for &mut __Dest<'_, D>
+{
+    fn copy_from<S: AsRef<[T]> + ?Sized>(&mut self, start: usize, src: &S) {
+        for (i, &value) in src.as_ref().iter().enumerate() {
+            self.0[start + i] = value;
+        }
+    }
+}
+
+/// Wraps an array for `arr_traced!`, and records every index that is
+/// assigned. It can't be borrowed mutably as a whole, so every element must
+/// be assigned by index.
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[doc(hidden)]
+pub struct __Traced<A> {
+    arr: A,
+    indices: alloc::vec::Vec<usize>,
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<A> __Traced<A> {
+    pub fn new(arr: A) -> Self {
+        __Traced {
+            arr,
+            indices: alloc::vec::Vec::new(),
+        }
+    }
+
+    pub fn finish(self) -> (A, alloc::vec::Vec<usize>) {
+        (self.arr, self.indices)
+    }
+
+    pub fn copy_within<T: Copy>(&mut self, src: core::ops::Range<usize>, dest: usize)
+    where
+        A: AsMut<[T]>,
+    {
+        self.indices.extend(dest..dest + (src.end - src.start));
+        self.arr.as_mut().copy_within(src, dest);
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<A> core::ops::Deref for __Traced<A> {
+    type Target = A;
+
+    fn deref(&self) -> &A {
+        &self.arr
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<T, A: AsRef<[T]>> AsRef<[T]> for __Traced<A> {
+    fn as_ref(&self) -> &[T] {
+        self.arr.as_ref()
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<A: core::ops::Index<usize>> core::ops::Index<usize> for __Traced<A> {
+    type Output = A::Output;
+
+    fn index(&self, index: usize) -> &A::Output {
+        &self.arr[index]
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<A: core::ops::IndexMut<usize>> core::ops::IndexMut<usize> for __Traced<A> {
+    fn index_mut(&mut self, index: usize) -> &mut A::Output {
+        self.indices.push(index);
+        &mut self.arr[index]
+    }
+}
+
+/// Wraps a slice for `arr_from_slice!`. The caller checks that its length is
+/// the length of the array.
+#[doc(hidden)]
+pub struct __FromSlice<'a, T>(pub &'a [T]);
+
+/// Copies a slice into an array at once.
+#[doc(hidden)]
+pub trait __CopyToArray<T> {
+    fn to_array<const N: usize>(&self) -> [T; N];
+}
+
+impl<T: Copy> __CopyToArray<T> for __FromSlice<'_, T> {
+    fn to_array<const N: usize>(&self) -> [T; N] {
+        let arr: &[T; N] =
+            core::convert::TryInto::try_into(self.0).expect("array-lit: wrong slice length");
+        *arr
+    }
+}
+
+/// Fallback for elements that aren't `Copy`, which clones them one by one.
+#[doc(hidden)]
+pub trait __CloneToArray<T> {
+    fn to_array<const N: usize>(&self) -> [T; N];
+}
+
+impl<T: Clone> __CloneToArray<T> for &__FromSlice<'_, T> {
+    fn to_array<const N: usize>(&self) -> [T; N] {
+        core::array::from_fn(|i| self.0[i].clone())
+    }
+}
+
+#[cfg(feature = "check-overlap")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __check_overlap {
+    ($($body:tt)*) => {
+        $crate::arr!(impl @ranges overlap () [] $($body)*);
+    };
+}
+
+#[cfg(not(feature = "check-overlap"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __check_overlap {
+    ($($body:tt)*) => {};
+}
+
+#[cfg(feature = "outline-loops")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __fill {
+    ($arr:ident ($start:expr) (.. $end:expr) ($step:expr) $value:expr) => {
+        $crate::__fill_range($start, $end, $step, &mut |i| {
+            $crate::arr!(impl @bounds [] $arr i);
+            $arr[i] = $value;
+        });
+    };
+    ($arr:ident ($start:expr) (..= $end:expr) ($step:expr) $value:expr) => {
+        $crate::__fill_range_inclusive($start, $end, $step, &mut |i| {
+            $crate::arr!(impl @bounds [] $arr i);
+            $arr[i] = $value;
+        });
+    };
+}
+
+#[cfg(not(feature = "outline-loops"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __fill {
+    ($($args:tt)*) => {
+        $crate::arr!(impl @loop [] $($args)*);
+    };
+}
+
+#[cfg(feature = "sorted-indices")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __sort_entries {
+    ($arr:ident { $($body:tt)* }) => {
+        $crate::arr!(impl @ranges sort ($arr { $($body)* }) [] $($body)*);
+    };
+}
+
+#[cfg(not(feature = "sorted-indices"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __sort_entries {
+    ($arr:ident { $($body:tt)* }) => {
+        $crate::arr!(impl $arr @entries [] { $($body)* });
+    };
+}
+
+// An index in parentheses is split into terms at `+` and `-`, and every term
+// into factors at `*`, `/` and `%`. These are then combined from left to right
+// with the `__checked_*` functions. An operator at the start of a term or
+// factor is unary, e.g. a dereference
+#[cfg(feature = "checked-indices")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __index {
+    (( $($expr:tt)+ )) => {
+        $crate::__index!(@terms [] [] $($expr)+)
+    };
+    ($key:tt) => {
+        $key
+    };
+
+    (@terms [$($terms:tt)*] [$($term:tt)+]) => {
+        $crate::__index!(@sum [$($terms)* + ($($term)+)])
+    };
+    (@terms [$($terms:tt)*] [$($term:tt)+] + $($rest:tt)*) => {
+        $crate::__index!(@terms [$($terms)* + ($($term)+)] [] $($rest)*)
+    };
+    (@terms [$($terms:tt)*] [$($term:tt)+] - $($rest:tt)*) => {
+        $crate::__index!(@terms [$($terms)* + ($($term)+) -] [] $($rest)*)
+    };
+    (@terms $terms:tt [$($term:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::__index!(@terms $terms [$($term)* $next] $($rest)*)
+    };
+    // a `-` is stored as `+ (a) -`, so every term is preceded by `+`
+    (@sum [+ ($($first:tt)+) $($rest:tt)*]) => {
+        $crate::__index!(@fold_sum ($crate::__index!(@factors [] [] $($first)+)) $($rest)*)
+    };
+    (@fold_sum $acc:tt) => {
+        $acc
+    };
+    (@fold_sum $acc:tt - + ($($term:tt)+) $($rest:tt)*) => {
+        $crate::__index!(@fold_sum
+            ($crate::__checked_sub($acc, $crate::__index!(@factors [] [] $($term)+)))
+            $($rest)*
+        )
+    };
+    (@fold_sum $acc:tt + ($($term:tt)+) $($rest:tt)*) => {
+        $crate::__index!(@fold_sum
+            ($crate::__checked_add($acc, $crate::__index!(@factors [] [] $($term)+)))
+            $($rest)*
+        )
+    };
+
+    (@factors [$($factors:tt)*] [$($factor:tt)+]) => {
+        $crate::__index!(@product [$($factors)* ($($factor)+)])
+    };
+    (@factors [$($factors:tt)*] [$($factor:tt)+] * $($rest:tt)*) => {
+        $crate::__index!(@factors [$($factors)* ($($factor)+) *] [] $($rest)*)
+    };
+    (@factors [$($factors:tt)*] [$($factor:tt)+] / $($rest:tt)*) => {
+        $crate::__index!(@factors [$($factors)* ($($factor)+) /] [] $($rest)*)
+    };
+    (@factors [$($factors:tt)*] [$($factor:tt)+] % $($rest:tt)*) => {
+        $crate::__index!(@factors [$($factors)* ($($factor)+) %] [] $($rest)*)
+    };
+    (@factors $factors:tt [$($factor:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::__index!(@factors $factors [$($factor)* $next] $($rest)*)
+    };
+    (@product [$first:tt $($rest:tt)*]) => {
+        $crate::__index!(@fold_product ($crate::__index!(@factor $first)) $($rest)*)
+    };
+    (@fold_product $acc:tt) => {
+        $acc
+    };
+    (@fold_product $acc:tt * $factor:tt $($rest:tt)*) => {
+        $crate::__index!(@fold_product
+            ($crate::__checked_mul($acc, $crate::__index!(@factor $factor)))
+            $($rest)*
+        )
+    };
+    (@fold_product $acc:tt $op:tt $factor:tt $($rest:tt)*) => {
+        $crate::__index!(@fold_product ($acc $op $crate::__index!(@factor $factor)) $($rest)*)
+    };
+    // nested parentheses are checked as well
+    (@factor (( $($expr:tt)+ ))) => {
+        $crate::__index!(( $($expr)+ ))
+    };
+    (@factor ( $($expr:tt)+ )) => {
+        ($($expr)+)
+    };
+}
+
+#[cfg(not(feature = "checked-indices"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __index {
+    ($key:tt) => {
+        $key
+    };
+}
+
+/// Reports an error at compile time. The messages of all diagnostics are
+/// collected here, so they are worded consistently.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __error {
+    // invalid syntax, reported with `compile_error!`
+    (fat_arrow [$($index:tt)+]) => {
+        compile_error!(concat!(
+            "expected `:` instead of `=>` after the index `",
+            stringify!($($index)+),
+            "`",
+        ))
+    };
+    (missing_colon [$($index:tt)+]) => {
+        compile_error!(concat!(
+            "expected `:` after the index `",
+            stringify!($($index)+),
+            "`; to insert several values at once, write `[start]: [a, b, c]`",
+        ))
+    };
+    (missing_guard_colon [$($guard:tt)*]) => {
+        compile_error!(concat!(
+            "expected `:` after the guard `",
+            stringify!($($guard)*),
+            "`",
+        ))
+    };
+    (unsupported $what:tt [$($entry:tt)*]) => {
+        compile_error!(concat!(
+            "`",
+            stringify!($what),
+            "` can't be used with the entry `",
+            stringify!($($entry)*),
+            "`",
+        ))
+    };
+    (infer_len_non_literal) => {
+        compile_error!("the length can only be inferred if all indices are literals")
+    };
+    (infer_len_entry [$($entry:tt)*]) => {
+        compile_error!(concat!(
+            "the length can't be inferred from the entry `",
+            stringify!($($entry)*),
+            "`",
+        ))
+    };
+    (omitted_fill) => {
+        compile_error!(
+            "the fill value can only be omitted if the first entry is `..`, which sets every element"
+        )
+    };
+    (prev_not_range [$($index:tt)+]) => {
+        compile_error!(concat!(
+            "`|prev, i|` can only be used with a range, found `",
+            stringify!($($index)+),
+            "`",
+        ))
+    };
+    (try_unsupported $name:ident) => {
+        compile_error!(concat!(
+            "`",
+            stringify!($name),
+            "!` doesn't support this form, because its indices can't be checked",
+        ))
+    };
+    (remove_not_last) => {
+        compile_error!("`remove` entries must come after all other entries")
+    };
+    (index_3d [$($index:tt)*]) => {
+        compile_error!(concat!(
+            "expected an index with three coordinates `(x, y, z)`, found `(",
+            stringify!($($index)*),
+            ")`",
+        ))
+    };
+    (concat_not_array $arg:tt) => {
+        compile_error!(concat!(
+            "expected an array or a constant in `arr_concat!`, found `",
+            stringify!($arg),
+            "`",
+        ))
+    };
+
+    // checks of literal indices and lengths, which are evaluated in a
+    // `const` item and fail to compile when the condition is false
+    (const out_of_bounds $cond:expr) => {
+        const _: () = assert!($cond, "array-lit: index out of bounds");
+    };
+    (const overlap $cond:expr) => {
+        const _: () = assert!($cond, "array-lit: overlapping indices");
+    };
+    (const duplicate $cond:expr) => {
+        const _: () = assert!($cond, "array-lit: duplicate index");
+    };
+    (const not_covered $cond:expr) => {
+        const _: () = assert!($cond, "array-lit: every index must be assigned exactly once");
+    };
+    (const column_len $cond:expr) => {
+        const _: () = assert!($cond, "array-lit: a column must have one value per row");
+    };
+    (const flat_len $cond:expr) => {
+        const _: () = assert!($cond, "array-lit: the number of values must be rows * columns");
+    };
+    (const prefix_len $cond:expr) => {
+        const _: () = assert!($cond, "array-lit: more values were given than the length of the array");
+    };
+    (const transpose_rows $cond:expr) => {
+        const _: () = assert!($cond, "array-lit: the number of rows doesn't match the dimensions");
+    };
+    (const transpose_row_len $cond:expr) => {
+        const _: () = assert!($cond, "array-lit: a row must have one value per column");
+    };
+}
+
+/// A macro for array literals with superpowers.
+///
+/// See [the module level documentation](index.html) for more.
+///
+/// # Example
+///
+///```rust
+/// # use array_lit::arr;
+/// let a = arr![1; 5; { [0]: [1, 2], 4: 0 }];
+/// assert_eq!(a, [1, 2, 1, 1, 0]);
+/// ```
+#[macro_export]
+macro_rules! arr {
+    // an explicit element type after the block
+    [fn $f:expr ; $len:expr ; { $($body:tt)* } : $ty:ty] => {
+        $crate::__typed::<$ty, _>($crate::arr![fn $f ; $len ; { $($body)* }])
+    };
+    [default ; $len:expr ; { $($body:tt)* } : $ty:ty] => {
+        $crate::__typed::<$ty, _>($crate::arr![default ; $len ; { $($body)* }])
+    };
+    [each $item:expr ; $len:expr ; { $($body:tt)* } : $ty:ty] => {
+        $crate::__typed::<$ty, _>($crate::arr![each $item ; $len ; { $($body)* }])
+    };
+    [clone $item:expr ; $len:expr ; { $($body:tt)* } : $ty:ty] => {
+        $crate::__typed::<$ty, _>($crate::arr![clone $item ; $len ; { $($body)* }])
+    };
+    [_ ; $len:expr ; { $($body:tt)* } : $ty:ty] => {
+        $crate::__typed::<$ty, _>($crate::arr![_ ; $len ; { $($body)* }])
+    };
+    [fn $g:expr ; $len:expr ; { $($body:tt)* } then $f:expr] => {
+        {
+            let mut arr = $crate::arr![fn $g ; $len ; { $($body)* }];
+            arr.iter_mut().for_each($f);
+            arr
+        }
+    };
+    [default ; $len:expr ; { $($body:tt)* } then $f:expr] => {
+        {
+            let mut arr = $crate::arr![default ; $len ; { $($body)* }];
+            arr.iter_mut().for_each($f);
+            arr
+        }
+    };
+    [each $g:expr ; $len:expr ; { $($body:tt)* } then $f:expr] => {
+        {
+            let mut arr = $crate::arr![each $g ; $len ; { $($body)* }];
+            arr.iter_mut().for_each($f);
+            arr
+        }
+    };
+    [clone $item:expr ; $len:expr ; { $($body:tt)* } then $f:expr] => {
+        {
+            let mut arr = $crate::arr![clone $item ; $len ; { $($body)* }];
+            arr.iter_mut().for_each($f);
+            arr
+        }
+    };
+    [fn $f:expr ; $len:expr ; { $($body:tt)* }] => {
+        {
+            #[allow(unused_mut, unused_assignments, unused_parens)]
+            {
+                let mut arr = $crate::arr![fn $f ; $len];
+                $crate::arr!(impl arr @block { $($body)* });
+                arr
+            }
+        }
+    };
+    [fn $f:expr ; $len:expr] => {
+        ::core::array::from_fn::<_, { $len }, _>($f)
+    };
+    [default ; $len:expr ; { $($body:tt)* }] => {
+        {
+            #[allow(unused_mut, unused_assignments, unused_parens)]
+            {
+                let mut arr = $crate::arr![default ; $len];
+                $crate::arr!(impl arr @block { $($body)* });
+                arr
+            }
+        }
+    };
+    [default ; $len:expr] => {
+        ::core::array::from_fn::<_, { $len }, _>(|_| ::core::default::Default::default())
+    };
+    [each $item:expr ; $len:expr ; { $($body:tt)* }] => {
+        {
+            #[allow(unused_mut, unused_assignments, unused_parens)]
+            {
+                let mut arr = $crate::arr![each $item ; $len];
+                $crate::arr!(impl arr @block { $($body)* });
+                arr
+            }
+        }
+    };
+    [each $item:expr ; $len:expr] => {
+        ::core::array::from_fn::<_, { $len }, _>(|_| $item)
+    };
+    [clone $item:expr ; $len:expr ; { $($body:tt)* }] => {
+        {
+            #[allow(unused_mut, unused_assignments, unused_parens)]
+            {
+                let mut arr = $crate::arr![clone $item ; $len];
+                $crate::arr!(impl arr @block { $($body)* });
+                arr
+            }
+        }
+    };
+    [clone $item:expr ; $len:expr] => {
+        $crate::__clone_array::<_, { $len }>($item)
+    };
+    [collect $iter:expr ; $len:expr] => {
+        {
+            let mut iter = ::core::iter::IntoIterator::into_iter($iter);
+            ::core::array::from_fn::<_, { $len }, _>(|i| match iter.next() {
+                ::core::option::Option::Some(item) => item,
+                ::core::option::Option::None => panic!(
+                    "the iterator yielded {} items, but {} were expected",
+                    i,
+                    $len,
+                ),
+            })
+        }
+    };
+    [_ ; $len:expr ; { .. : fn $f:expr $(, $($body:tt)*)? }] => {
+        $crate::arr![fn $f ; $len ; { $($($body)*)? }]
+    };
+    [_ ; $len:expr ; { .. : $item:expr $(, $($body:tt)*)? }] => {
+        $crate::arr![each $item ; $len ; { $($($body)*)? }]
+    };
+    [_ ; $len:expr ; { $($body:tt)* }] => {
+        $crate::__error!(omitted_fill)
+    };
+    [$item:expr ; _ ; { $($body:tt)* } : $ty:ty] => {
+        $crate::__typed::<$ty, _>($crate::arr![$item ; _ ; { $($body)* }])
+    };
+    [$item:expr ; $len:expr ; { $($body:tt)* } : $ty:ty] => {
+        $crate::__typed::<$ty, _>($crate::arr![$item ; $len ; { $($body)* }])
+    };
+    [$item:expr ; $len:expr ; { $($body:tt)* } then $f:expr] => {
+        {
+            let mut arr = $crate::arr![$item ; $len ; { $($body)* }];
+            arr.iter_mut().for_each($f);
+            arr
+        }
+    };
+    [$item:expr ; $len:tt ; as $a:ident { $($body:tt)* }] => {
+        $crate::arr!(impl @as ($item ; $len) $a [] [] $($body)*)
+    };
+    [$item:expr ; $len:expr ; as $a:ident { $($body:tt)* }] => {
+        $crate::arr!(impl @as ($item ; $len) $a [] [] $($body)*)
+    };
+    [$item:expr ; _ ; { $($body:tt)* }] => {
+        {
+            #[allow(unused_mut, unused_assignments, unused_parens)]
+            {
+                let mut arr = [$item ; $crate::arr!(impl @ranges len () [] $($body)*)];
+                $crate::arr!(impl arr @block { $($body)* });
+                arr
+            }
+        }
+    };
+    [$item:expr ; $len:literal ; { $($body:tt)* }] => {
+        {
+            #[allow(unused_mut, unused_assignments, unused_parens)]
+            {
+                $crate::arr!(impl @check $len { $($body)* });
+                let mut arr = [$item ; $len];
+                $crate::arr!(impl arr @block { $($body)* });
+                arr
+            }
+        }
+    };
+    [$item:expr ; $len:expr ; { $($body:tt)* }] => {
+        {
+            #[allow(unused_mut, unused_assignments, unused_parens)]
+            {
+                let mut arr = [$item ; $len];
+                $crate::arr!(impl arr @block { $($body)* });
+                arr
+            }
+        }
+    };
+
+    // same syntax as regular array literals:
+    [$item:expr ; $len:expr] => {
+        [$item ; $len]
+    };
+    // a list of values at the start, followed by the length and the fill value
+    [$( $value:expr ),+ $(,)? ; $len:literal ; $fill:expr] => {
+        {
+            $crate::__error!(const prefix_len [$($crate::arr!(impl @one $value)),+].len() <= $len);
+            $crate::arr!(impl @prefix [$($value),+] ($len) $fill)
+        }
+    };
+    [$( $value:expr ),+ $(,)? ; $len:expr ; $fill:expr] => {
+        {
+            let count = 0 $(+ $crate::arr!(impl @one $value))+;
+            assert!(
+                count <= $len,
+                "array-lit: {} values were given, but the length is {}",
+                count,
+                $len,
+            );
+            $crate::arr!(impl @prefix [$($value),+] ($len) $fill)
+        }
+    };
+    [$( $item:expr ),* $(,)?] => {
+        [ $($item),* ]
+    };
+
+    // Implementation details:
+    // turns every `index: value` entry of a block with a named array into
+    // `index: |a| value`, then creates the array from the new block
+    (impl @as (@const $($head:tt)*) $a:ident [$($done:tt)*] []) => {
+        $crate::const_arr![$($head)* ; { $($done)* }]
+    };
+    (impl @as ($($head:tt)*) $a:ident [$($done:tt)*] []) => {
+        $crate::arr![$($head)* ; { $($done)* }]
+    };
+    (impl @as $head:tt $a:ident [$($done:tt)*] [] , $($rest:tt)*) => {
+        $crate::arr!(impl @as $head $a [$($done)*] [] $($rest)*)
+    };
+    (impl @as $head:tt $a:ident [$($done:tt)*] []
+        base $base:tt { $($inner:tt)* } $(, $($rest:tt)*)?
+    ) => {
+        $crate::arr!(impl @as $head $a [$($done)* base $base { $($inner)* },] [] $($($rest)*)?)
+    };
+    (impl @as $head:tt $a:ident [$($done:tt)*] []
+        from_pairs $pairs:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::arr!(impl @as $head $a [$($done)* from_pairs $pairs,] [] $($($rest)*)?)
+    };
+    (impl @as $head:tt $a:ident [$($done:tt)*] [] mirror $(, $($rest:tt)*)?) => {
+        $crate::arr!(impl @as $head $a [$($done)* mirror,] [] $($($rest)*)?)
+    };
+    // these values are kept as they are
+    (impl @as $head:tt $a:ident [$($done:tt)*] [$($index:tt)+]
+        : [ $($value:tt)* ] * $count:tt $(, $($rest:tt)*)?
+    ) => {
+        $crate::arr!(impl @as $head $a
+            [$($done)* $($index)+ : [ $($value)* ] * $count,] [] $($($rest)*)?
+        )
+    };
+    (impl @as $head:tt $a:ident [$($done:tt)*] [$($index:tt)+]
+        : $src:tt [ $($range:tt)* ] of $len:tt $(, $($rest:tt)*)?
+    ) => {
+        $crate::arr!(impl @as $head $a
+            [$($done)* $($index)+ : $src [ $($range)* ] of $len,] [] $($($rest)*)?
+        )
+    };
+    (impl @as $head:tt $a:ident [$($done:tt)*] [$($index:tt)+]
+        : from $src:tt $(, $($rest:tt)*)?
+    ) => {
+        $crate::arr!(impl @as $head $a [$($done)* $($index)+ : from $src,] [] $($($rest)*)?)
+    };
+    (impl @as $head:tt $a:ident [$($done:tt)*] [$($index:tt)+]
+        : fn $f:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::arr!(impl @as $head $a [$($done)* $($index)+ : fn $f,] [] $($($rest)*)?)
+    };
+    (impl @as $head:tt $a:ident [$($done:tt)*] [$($index:tt)+]
+        : | $prev:tt , $i:tt | $value:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::arr!(impl @as $head $a [$($done)* $($index)+ : | $prev , $i | $value,] [] $($($rest)*)?)
+    };
+    (impl @as $head:tt $a:ident [$($done:tt)*] [$($index:tt)+]
+        : | $b:ident | $value:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::arr!(impl @as $head $a [$($done)* $($index)+ : | $b | $value,] [] $($($rest)*)?)
+    };
+    (impl @as $head:tt $a:ident [$($done:tt)*] [$($index:tt)+] : += $($rest:tt)*) => {
+        $crate::arr!(impl @as $head $a [$($done)*] [$($index)+] @op (+=) $($rest)*)
+    };
+    (impl @as $head:tt $a:ident [$($done:tt)*] [$($index:tt)+] : -= $($rest:tt)*) => {
+        $crate::arr!(impl @as $head $a [$($done)*] [$($index)+] @op (-=) $($rest)*)
+    };
+    (impl @as $head:tt $a:ident [$($done:tt)*] [$($index:tt)+] : *= $($rest:tt)*) => {
+        $crate::arr!(impl @as $head $a [$($done)*] [$($index)+] @op (*=) $($rest)*)
+    };
+    (impl @as $head:tt $a:ident [$($done:tt)*] [$($index:tt)+] : /= $($rest:tt)*) => {
+        $crate::arr!(impl @as $head $a [$($done)*] [$($index)+] @op (/=) $($rest)*)
+    };
+    (impl @as $head:tt $a:ident [$($done:tt)*] [$($index:tt)+] : %= $($rest:tt)*) => {
+        $crate::arr!(impl @as $head $a [$($done)*] [$($index)+] @op (%=) $($rest)*)
+    };
+    (impl @as $head:tt $a:ident [$($done:tt)*] [$($index:tt)+] : &= $($rest:tt)*) => {
+        $crate::arr!(impl @as $head $a [$($done)*] [$($index)+] @op (&=) $($rest)*)
+    };
+    (impl @as $head:tt $a:ident [$($done:tt)*] [$($index:tt)+] : |= $($rest:tt)*) => {
+        $crate::arr!(impl @as $head $a [$($done)*] [$($index)+] @op (|=) $($rest)*)
+    };
+    (impl @as $head:tt $a:ident [$($done:tt)*] [$($index:tt)+] : ^= $($rest:tt)*) => {
+        $crate::arr!(impl @as $head $a [$($done)*] [$($index)+] @op (^=) $($rest)*)
+    };
+    (impl @as $head:tt $a:ident [$($done:tt)*] [$($index:tt)+] : <<= $($rest:tt)*) => {
+        $crate::arr!(impl @as $head $a [$($done)*] [$($index)+] @op (<<=) $($rest)*)
+    };
+    (impl @as $head:tt $a:ident [$($done:tt)*] [$($index:tt)+] : >>= $($rest:tt)*) => {
+        $crate::arr!(impl @as $head $a [$($done)*] [$($index)+] @op (>>=) $($rest)*)
+    };
+    (impl @as $head:tt $a:ident [$($done:tt)*] [$($index:tt)+]
+        @op ($op:tt) | $b:ident | $value:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::arr!(impl @as $head $a [$($done)* $($index)+ : $op | $b | $value,] [] $($($rest)*)?)
+    };
+    (impl @as $head:tt $a:ident [$($done:tt)*] [$($index:tt)+]
+        @op ($op:tt) $value:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::arr!(impl @as $head $a
+            [$($done)* $($index)+ : $op | $a | { let _ = $a; $value },] [] $($($rest)*)?
+        )
+    };
+    // the name doesn't have to be used in every entry
+    (impl @as $head:tt $a:ident [$($done:tt)*] [$($index:tt)+]
+        : $value:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::arr!(impl @as $head $a
+            [$($done)* $($index)+ : | $a | { let _ = $a; $value },] [] $($($rest)*)?
+        )
+    };
+    // invalid entries are passed on, so `arr!` can report the error
+    (impl @as ($($head:tt)*) $a:ident [$($done:tt)*] [$($index:tt)+] => $($rest:tt)*) => {
+        $crate::arr![$($head)* ; { $($done)* $($index)+ => $($rest)* }]
+    };
+    (impl @as ($($head:tt)*) $a:ident [$($done:tt)*] [$($index:tt)+] $(, $($rest:tt)*)?) => {
+        $crate::arr![$($head)* ; { $($done)* $($index)+ $(, $($rest)*)? }]
+    };
+    (impl @as $head:tt $a:ident [$($done:tt)*] [$($index:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::arr!(impl @as $head $a [$($done)*] [$($index)* $next] $($rest)*)
+    };
+    (impl @check $len:literal { , $($body:tt)* }) => {
+        $crate::arr!(impl @check $len { $($body)* });
+    };
+    (impl @check $len:literal { $( $index:tt : $value:expr ),* $(,)? }) => {
+        $( $crate::arr!(impl @check $len $index); )*
+    };
+    (impl @check $len:literal { $($body:tt)* }) => {};
+    (impl @check $len:literal $index:literal) => {
+        $crate::__error!(const out_of_bounds $index < $len);
+    };
+    (impl @check $len:literal $index:tt) => {};
+
+    // collects the index ranges of entries with literal indices, and passes
+    // them to `$then` together with `$ctx`, or `!` if an index isn't a literal
+    (impl @ranges $then:ident $ctx:tt [$($ranges:tt)*] $(,)?) => {
+        $crate::arr!(impl @$then $ctx [$($ranges)*])
+    };
+    // up to sixteen entries are collected at once, see `@munch`
+    (impl @ranges $then:ident $ctx:tt [$($ranges:tt)*]
+        $i1:literal : $v1:expr , $i2:literal : $v2:expr , $i3:literal : $v3:expr , $i4:literal : $v4:expr , $i5:literal : $v5:expr , $i6:literal : $v6:expr , $i7:literal : $v7:expr , $i8:literal : $v8:expr , $i9:literal : $v9:expr , $i10:literal : $v10:expr , $i11:literal : $v11:expr , $i12:literal : $v12:expr , $i13:literal : $v13:expr , $i14:literal : $v14:expr , $i15:literal : $v15:expr , $i16:literal : $v16:expr
+        $(, $($rest:tt)*)?
+    ) => {
+        $crate::arr!(impl @ranges $then $ctx
+            [$($ranges)* ($i1, $i1 + 1), ($i2, $i2 + 1), ($i3, $i3 + 1), ($i4, $i4 + 1), ($i5, $i5 + 1), ($i6, $i6 + 1), ($i7, $i7 + 1), ($i8, $i8 + 1), ($i9, $i9 + 1), ($i10, $i10 + 1), ($i11, $i11 + 1), ($i12, $i12 + 1), ($i13, $i13 + 1), ($i14, $i14 + 1), ($i15, $i15 + 1), ($i16, $i16 + 1),]
+            $($($rest)*)?
+        )
+    };
+    (impl @ranges $then:ident $ctx:tt [$($ranges:tt)*] $index:literal : $value:expr $(, $($rest:tt)*)?) => {
+        $crate::arr!(impl @ranges $then $ctx [$($ranges)* ($index, $index + 1),] $($($rest)*)?)
+    };
+    (impl @ranges $then:ident $ctx:tt [$($ranges:tt)*] $start:literal .. $end:literal : $value:expr $(, $($rest:tt)*)?) => {
+        $crate::arr!(impl @ranges $then $ctx [$($ranges)* ($start, $end),] $($($rest)*)?)
+    };
+    (impl @ranges $then:ident $ctx:tt [$($ranges:tt)*] $start:literal ..= $end:literal : $value:expr $(, $($rest:tt)*)?) => {
+        $crate::arr!(impl @ranges $then $ctx [$($ranges)* ($start, $end + 1),] $($($rest)*)?)
+    };
+    (impl @ranges $then:ident $ctx:tt [$($ranges:tt)*] [$start:literal] : [ $value:expr ; $len:literal ] $(, $($rest:tt)*)?) => {
+        $crate::arr!(impl @ranges $then $ctx [$($ranges)* ($start, $start + $len),] $($($rest)*)?)
+    };
+    (impl @ranges $then:ident $ctx:tt [$($ranges:tt)*] [$start:literal] : [ $($value:expr),* $(,)? ] $(, $($rest:tt)*)?) => {
+        $crate::arr!(impl @ranges $then $ctx
+            [$($ranges)* ($start, $start $(+ $crate::arr!(impl @one $value))*),]
+            $($($rest)*)?
+        )
+    };
+    (impl @ranges $then:ident $ctx:tt [$($ranges:tt)*] $label:literal [ $($next:tt)* ] $($rest:tt)*) => {
+        $crate::arr!(impl @ranges $then $ctx [$($ranges)*] [ $($next)* ] $($rest)*)
+    };
+    (impl @ranges $then:ident $ctx:tt [$($ranges:tt)*] $label:literal $next:literal $($rest:tt)*) => {
+        $crate::arr!(impl @ranges $then $ctx [$($ranges)*] $next $($rest)*)
+    };
+    // empty entries are skipped
+    (impl @ranges $then:ident $ctx:tt [$($ranges:tt)*] , $($rest:tt)*) => {
+        $crate::arr!(impl @ranges $then $ctx [$($ranges)*] $($rest)*)
+    };
+    (impl @ranges $then:ident $ctx:tt [$($ranges:tt)*] $($rest:tt)*) => {
+        $crate::arr!(impl @$then $ctx !)
+    };
+
+    (impl @overlap () [$($ranges:tt)*]) => {
+        $crate::__error!(const overlap !$crate::__has_overlap(&[$($ranges)*]));
+    };
+    // indices that aren't literals can't be checked
+    (impl @overlap () !) => {};
+
+    (impl @len () [$($ranges:tt)*]) => {
+        $crate::__max_end(&[$($ranges)*])
+    };
+    (impl @len () !) => {
+        $crate::__error!(infer_len_non_literal)
+    };
+    (impl @one $value:expr) => { 1 };
+    (impl @prefix [$($value:expr),+] ($len:expr) $fill:expr) => {
+        {
+            #[allow(unused_assignments)]
+            {
+                let mut arr = [$fill ; $len];
+                let mut i = 0;
+                $(
+                    arr[i] = $value;
+                    i += 1;
+                )+
+                arr
+            }
+        }
+    };
+
+    (impl @sort ($arr:ident { $($body:tt)* }) [$($ranges:tt)*]) => {
+        #[allow(unused_variables)]
+        let ranges: &[(usize, usize)] = &[$($ranges)*];
+        $crate::arr!(impl $arr @sorted ranges (0) $($body)*);
+    };
+    // indices that aren't literals can't be sorted
+    (impl @sort ($arr:ident { $($body:tt)* }) !) => {
+        $crate::arr!(impl $arr @entries [] { $($body)* });
+    };
+    // applies the entries in source order, but skips elements that are
+    // overwritten by an entry with a greater start index afterwards
+    (impl $arr:ident @sorted $ranges:ident ($($n:tt)*) $(,)?) => {};
+    (impl $arr:ident @sorted $ranges:ident ($($n:tt)*) , $($rest:tt)*) => {
+        $crate::arr!(impl $arr @sorted $ranges ($($n)*) $($rest)*);
+    };
+    (impl $arr:ident @sorted $ranges:ident ($($n:tt)*) $label:literal [ $($next:tt)* ] $($rest:tt)*) => {
+        let _: &str = $label;
+        $crate::arr!(impl $arr @sorted $ranges ($($n)*) [ $($next)* ] $($rest)*);
+    };
+    (impl $arr:ident @sorted $ranges:ident ($($n:tt)*) $label:literal $next:literal $($rest:tt)*) => {
+        let _: &str = $label;
+        $crate::arr!(impl $arr @sorted $ranges ($($n)*) $next $($rest)*);
+    };
+    (impl $arr:ident @sorted $ranges:ident ($($n:tt)*)
+        $index:literal : | $prev:tt , $i:tt | $value:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::arr!(impl @prev [] $arr [$index] | $prev , $i | $value);
+    };
+    (impl $arr:ident @sorted $ranges:ident ($($n:tt)*)
+        $index:literal : | $a:ident | $value:expr $(, $($rest:tt)*)?
+    ) => {
+        if $crate::__is_last_write($ranges, $($n)*, $index) {
+            let value = {
+                let $a = &$arr;
+                $value
+            };
+            $arr[$index] = value;
+        }
+        $crate::arr!(impl $arr @sorted $ranges ($($n)* + 1) $($($rest)*)?);
+    };
+    (impl $arr:ident @sorted $ranges:ident ($($n:tt)*)
+        $i1:literal : $v1:expr ,
+        $i2:literal : | $($rest:tt)*
+    ) => {
+        if $crate::__is_last_write($ranges, $($n)* + 0, $i1) {
+            $arr[$i1] = $v1;
+        }
+        $crate::arr!(impl $arr @sorted $ranges ($($n)* + 1) $i2 : | $($rest)*);
+    };
+    (impl $arr:ident @sorted $ranges:ident ($($n:tt)*)
+        $i1:literal : $v1:expr , $i2:literal : $v2:expr ,
+        $i3:literal : | $($rest:tt)*
+    ) => {
+        if $crate::__is_last_write($ranges, $($n)* + 0, $i1) {
+            $arr[$i1] = $v1;
+        }
+        if $crate::__is_last_write($ranges, $($n)* + 1, $i2) {
+            $arr[$i2] = $v2;
+        }
+        $crate::arr!(impl $arr @sorted $ranges ($($n)* + 2) $i3 : | $($rest)*);
+    };
+    (impl $arr:ident @sorted $ranges:ident ($($n:tt)*)
+        $i1:literal : $v1:expr , $i2:literal : $v2:expr , $i3:literal : $v3:expr ,
+        $i4:literal : | $($rest:tt)*
+    ) => {
+        if $crate::__is_last_write($ranges, $($n)* + 0, $i1) {
+            $arr[$i1] = $v1;
+        }
+        if $crate::__is_last_write($ranges, $($n)* + 1, $i2) {
+            $arr[$i2] = $v2;
+        }
+        if $crate::__is_last_write($ranges, $($n)* + 2, $i3) {
+            $arr[$i3] = $v3;
+        }
+        $crate::arr!(impl $arr @sorted $ranges ($($n)* + 3) $i4 : | $($rest)*);
+    };
+    (impl $arr:ident @sorted $ranges:ident ($($n:tt)*)
+        $i1:literal : $v1:expr , $i2:literal : $v2:expr , $i3:literal : $v3:expr , $i4:literal : $v4:expr ,
+        $i5:literal : | $($rest:tt)*
+    ) => {
+        if $crate::__is_last_write($ranges, $($n)* + 0, $i1) {
+            $arr[$i1] = $v1;
+        }
+        if $crate::__is_last_write($ranges, $($n)* + 1, $i2) {
+            $arr[$i2] = $v2;
+        }
+        if $crate::__is_last_write($ranges, $($n)* + 2, $i3) {
+            $arr[$i3] = $v3;
+        }
+        if $crate::__is_last_write($ranges, $($n)* + 3, $i4) {
+            $arr[$i4] = $v4;
+        }
+        $crate::arr!(impl $arr @sorted $ranges ($($n)* + 4) $i5 : | $($rest)*);
+    };
+    (impl $arr:ident @sorted $ranges:ident ($($n:tt)*)
+        $i1:literal : $v1:expr , $i2:literal : $v2:expr , $i3:literal : $v3:expr , $i4:literal : $v4:expr , $i5:literal : $v5:expr ,
+        $i6:literal : | $($rest:tt)*
+    ) => {
+        if $crate::__is_last_write($ranges, $($n)* + 0, $i1) {
+            $arr[$i1] = $v1;
+        }
+        if $crate::__is_last_write($ranges, $($n)* + 1, $i2) {
+            $arr[$i2] = $v2;
+        }
+        if $crate::__is_last_write($ranges, $($n)* + 2, $i3) {
+            $arr[$i3] = $v3;
+        }
+        if $crate::__is_last_write($ranges, $($n)* + 3, $i4) {
+            $arr[$i4] = $v4;
+        }
+        if $crate::__is_last_write($ranges, $($n)* + 4, $i5) {
+            $arr[$i5] = $v5;
+        }
+        $crate::arr!(impl $arr @sorted $ranges ($($n)* + 5) $i6 : | $($rest)*);
+    };
+    (impl $arr:ident @sorted $ranges:ident ($($n:tt)*)
+        $i1:literal : $v1:expr , $i2:literal : $v2:expr , $i3:literal : $v3:expr , $i4:literal : $v4:expr , $i5:literal : $v5:expr , $i6:literal : $v6:expr ,
+        $i7:literal : | $($rest:tt)*
+    ) => {
+        if $crate::__is_last_write($ranges, $($n)* + 0, $i1) {
+            $arr[$i1] = $v1;
+        }
+        if $crate::__is_last_write($ranges, $($n)* + 1, $i2) {
+            $arr[$i2] = $v2;
+        }
+        if $crate::__is_last_write($ranges, $($n)* + 2, $i3) {
+            $arr[$i3] = $v3;
+        }
+        if $crate::__is_last_write($ranges, $($n)* + 3, $i4) {
+            $arr[$i4] = $v4;
+        }
+        if $crate::__is_last_write($ranges, $($n)* + 4, $i5) {
+            $arr[$i5] = $v5;
+        }
+        if $crate::__is_last_write($ranges, $($n)* + 5, $i6) {
+            $arr[$i6] = $v6;
+        }
+        $crate::arr!(impl $arr @sorted $ranges ($($n)* + 6) $i7 : | $($rest)*);
+    };
+    (impl $arr:ident @sorted $ranges:ident ($($n:tt)*)
+        $i1:literal : $v1:expr , $i2:literal : $v2:expr , $i3:literal : $v3:expr , $i4:literal : $v4:expr , $i5:literal : $v5:expr , $i6:literal : $v6:expr , $i7:literal : $v7:expr ,
+        $i8:literal : | $($rest:tt)*
+    ) => {
+        if $crate::__is_last_write($ranges, $($n)* + 0, $i1) {
+            $arr[$i1] = $v1;
+        }
+        if $crate::__is_last_write($ranges, $($n)* + 1, $i2) {
+            $arr[$i2] = $v2;
+        }
+        if $crate::__is_last_write($ranges, $($n)* + 2, $i3) {
+            $arr[$i3] = $v3;
+        }
+        if $crate::__is_last_write($ranges, $($n)* + 3, $i4) {
+            $arr[$i4] = $v4;
+        }
+        if $crate::__is_last_write($ranges, $($n)* + 4, $i5) {
+            $arr[$i5] = $v5;
+        }
+        if $crate::__is_last_write($ranges, $($n)* + 5, $i6) {
+            $arr[$i6] = $v6;
+        }
+        if $crate::__is_last_write($ranges, $($n)* + 6, $i7) {
+            $arr[$i7] = $v7;
+        }
+        $crate::arr!(impl $arr @sorted $ranges ($($n)* + 7) $i8 : | $($rest)*);
+    };
+    (impl $arr:ident @sorted $ranges:ident ($($n:tt)*)
+        $i1:literal : $v1:expr , $i2:literal : $v2:expr , $i3:literal : $v3:expr , $i4:literal : $v4:expr , $i5:literal : $v5:expr , $i6:literal : $v6:expr , $i7:literal : $v7:expr , $i8:literal : $v8:expr
+        $(, $($rest:tt)*)?
+    ) => {
+        if $crate::__is_last_write($ranges, $($n)* + 0, $i1) {
+            $arr[$i1] = $v1;
+        }
+        if $crate::__is_last_write($ranges, $($n)* + 1, $i2) {
+            $arr[$i2] = $v2;
+        }
+        if $crate::__is_last_write($ranges, $($n)* + 2, $i3) {
+            $arr[$i3] = $v3;
+        }
+        if $crate::__is_last_write($ranges, $($n)* + 3, $i4) {
+            $arr[$i4] = $v4;
+        }
+        if $crate::__is_last_write($ranges, $($n)* + 4, $i5) {
+            $arr[$i5] = $v5;
+        }
+        if $crate::__is_last_write($ranges, $($n)* + 5, $i6) {
+            $arr[$i6] = $v6;
+        }
+        if $crate::__is_last_write($ranges, $($n)* + 6, $i7) {
+            $arr[$i7] = $v7;
+        }
+        if $crate::__is_last_write($ranges, $($n)* + 7, $i8) {
+            $arr[$i8] = $v8;
+        }
+        $crate::arr!(impl $arr @sorted $ranges ($($n)* + 8) $($($rest)*)?);
+    };
+    (impl $arr:ident @sorted $ranges:ident ($($n:tt)*)
+        $index:literal : $value:expr $(, $($rest:tt)*)?
+    ) => {
+        if $crate::__is_last_write($ranges, $($n)*, $index) {
+            $arr[$index] = $value;
+        }
+        $crate::arr!(impl $arr @sorted $ranges ($($n)* + 1) $($($rest)*)?);
+    };
+    // the previous element must be up to date, so every element is assigned
+    (impl $arr:ident @sorted $ranges:ident ($($n:tt)*)
+        $start:literal .. $end:literal : | $prev:tt , $i:tt | $value:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::arr!(impl @prev [] $arr [$start .. $end] | $prev , $i | $value);
+        $crate::arr!(impl $arr @sorted $ranges ($($n)* + 1) $($($rest)*)?);
+    };
+    (impl $arr:ident @sorted $ranges:ident ($($n:tt)*)
+        $start:literal ..= $end:literal : | $prev:tt , $i:tt | $value:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::arr!(impl @prev [] $arr [$start ..= $end] | $prev , $i | $value);
+        $crate::arr!(impl $arr @sorted $ranges ($($n)* + 1) $($($rest)*)?);
+    };
+    (impl $arr:ident @sorted $ranges:ident ($($n:tt)*)
+        $start:literal .. $end:literal : | $a:ident | $value:expr $(, $($rest:tt)*)?
+    ) => {
+        let value = {
+            let $a = &$arr;
+            $value
+        };
+        $crate::arr!(impl $arr @sorted $ranges ($($n)*) $start .. $end : ::core::clone::Clone::clone(&value) $(, $($rest)*)?);
+    };
+    (impl $arr:ident @sorted $ranges:ident ($($n:tt)*)
+        $start:literal ..= $end:literal : | $a:ident | $value:expr $(, $($rest:tt)*)?
+    ) => {
+        let value = {
+            let $a = &$arr;
+            $value
+        };
+        $crate::arr!(impl $arr @sorted $ranges ($($n)*) $start ..= $end : ::core::clone::Clone::clone(&value) $(, $($rest)*)?);
+    };
+    (impl $arr:ident @sorted $ranges:ident ($($n:tt)*)
+        $start:literal .. $end:literal : $value:expr $(, $($rest:tt)*)?
+    ) => {
+        let mut i = $start;
+        while i < $end {
+            if $crate::__is_last_write($ranges, $($n)*, i) {
+                $arr[i] = $value;
+            }
+            i += 1;
+        }
+        $crate::arr!(impl $arr @sorted $ranges ($($n)* + 1) $($($rest)*)?);
+    };
+    (impl $arr:ident @sorted $ranges:ident ($($n:tt)*)
+        $start:literal ..= $end:literal : $value:expr $(, $($rest:tt)*)?
+    ) => {
+        let mut i = $start;
+        while i <= $end {
+            if $crate::__is_last_write($ranges, $($n)*, i) {
+                $arr[i] = $value;
+            }
+            i += 1;
+        }
+        $crate::arr!(impl $arr @sorted $ranges ($($n)* + 1) $($($rest)*)?);
+    };
+    (impl $arr:ident @sorted $ranges:ident ($($n:tt)*)
+        [$start:literal] : [ $value:expr ; $len:literal ] $(, $($rest:tt)*)?
+    ) => {
+        let mut i = $start;
+        while i < $start + $len {
+            if $crate::__is_last_write($ranges, $($n)*, i) {
+                $arr[i] = $value;
+            }
+            i += 1;
+        }
+        $crate::arr!(impl $arr @sorted $ranges ($($n)* + 1) $($($rest)*)?);
+    };
+    (impl $arr:ident @sorted $ranges:ident ($($n:tt)*)
+        [$start:literal] : [] $(, $($rest:tt)*)?
+    ) => {
+        $crate::arr!(impl $arr @sorted $ranges ($($n)* + 1) $($($rest)*)?);
+    };
+    (impl $arr:ident @sorted $ranges:ident ($($n:tt)*)
+        [$start:literal] : [ $($value:expr),* $(,)? ] $(, $($rest:tt)*)?
+    ) => {
+        let entry = $($n)*;
+        let mut i = $start;
+        $(
+            if $crate::__is_last_write($ranges, entry, i) {
+                $arr[i] = $value;
+            }
+            i += 1;
+        )*
+        $crate::arr!(impl $arr @sorted $ranges ($($n)* + 1) $($($rest)*)?);
+    };
+
+    (impl $arr:ident @block { $($body:tt)* }) => {
+        $crate::__check_overlap!($($body)*);
+        $crate::__sort_entries!($arr { $($body)* });
+    };
+    (impl $arr:ident @block @const { $($body:tt)* }) => {
+        $crate::__check_overlap!($($body)*);
+        $crate::arr!(impl $arr @entries [@const] { $($body)* });
+    };
+    (impl $arr:ident @entries $mode:tt { , $($body:tt)* }) => {
+        $crate::arr!(impl $arr @munch $mode [] $($body)*);
+    };
+    (impl $arr:ident @entries $mode:tt { $( $index:tt : $value:tt ),* $(,)? }) => {
+        $( $crate::arr!(impl $arr $mode { $index : $value }); )*
+    };
+    (impl $arr:ident @entries $mode:tt { $($body:tt)* }) => {
+        $crate::arr!(impl $arr @munch $mode [] $($body)*);
+    };
+    (impl $arr:ident @munch $mode:tt []) => {};
+    // empty entries are skipped
+    (impl $arr:ident @munch $mode:tt [] , $($rest:tt)*) => {
+        $crate::arr!(impl $arr @munch $mode [] $($rest)*);
+    };
+    // an entry with an attribute is applied in a block with that attribute,
+    // so it can be removed with `#[cfg]`
+    (impl $arr:ident @munch $mode:tt [] # [ $($attr:tt)* ] $($rest:tt)*) => {
+        $crate::arr!(impl $arr @attr $mode [$($attr)*] [] $($rest)*);
+    };
+    // up to eight entries with literal indices are applied at once, so large
+    // blocks don't exceed the recursion limit. A closure ends the batch,
+    // because it can't be told apart from other values once it is parsed
+    (impl $arr:ident @munch $mode:tt [] $i1:literal : | $($rest:tt)*) => {
+        $crate::arr!(impl $arr @munch $mode [$i1] : | $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt []
+        $i1:literal : $v1:expr ,
+        $i2:literal : | $($rest:tt)*
+    ) => {
+        $crate::arr!(impl $arr $mode { $i1 : $v1 });
+        $crate::arr!(impl $arr @munch $mode [$i2] : | $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt []
+        $i1:literal : $v1:expr , $i2:literal : $v2:expr ,
+        $i3:literal : | $($rest:tt)*
+    ) => {
+        $crate::arr!(impl $arr $mode { $i1 : $v1 });
+        $crate::arr!(impl $arr $mode { $i2 : $v2 });
+        $crate::arr!(impl $arr @munch $mode [$i3] : | $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt []
+        $i1:literal : $v1:expr , $i2:literal : $v2:expr , $i3:literal : $v3:expr ,
+        $i4:literal : | $($rest:tt)*
+    ) => {
+        $crate::arr!(impl $arr $mode { $i1 : $v1 });
+        $crate::arr!(impl $arr $mode { $i2 : $v2 });
+        $crate::arr!(impl $arr $mode { $i3 : $v3 });
+        $crate::arr!(impl $arr @munch $mode [$i4] : | $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt []
+        $i1:literal : $v1:expr , $i2:literal : $v2:expr , $i3:literal : $v3:expr , $i4:literal : $v4:expr ,
+        $i5:literal : | $($rest:tt)*
+    ) => {
+        $crate::arr!(impl $arr $mode { $i1 : $v1 });
+        $crate::arr!(impl $arr $mode { $i2 : $v2 });
+        $crate::arr!(impl $arr $mode { $i3 : $v3 });
+        $crate::arr!(impl $arr $mode { $i4 : $v4 });
+        $crate::arr!(impl $arr @munch $mode [$i5] : | $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt []
+        $i1:literal : $v1:expr , $i2:literal : $v2:expr , $i3:literal : $v3:expr , $i4:literal : $v4:expr , $i5:literal : $v5:expr ,
+        $i6:literal : | $($rest:tt)*
+    ) => {
+        $crate::arr!(impl $arr $mode { $i1 : $v1 });
+        $crate::arr!(impl $arr $mode { $i2 : $v2 });
+        $crate::arr!(impl $arr $mode { $i3 : $v3 });
+        $crate::arr!(impl $arr $mode { $i4 : $v4 });
+        $crate::arr!(impl $arr $mode { $i5 : $v5 });
+        $crate::arr!(impl $arr @munch $mode [$i6] : | $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt []
+        $i1:literal : $v1:expr , $i2:literal : $v2:expr , $i3:literal : $v3:expr , $i4:literal : $v4:expr , $i5:literal : $v5:expr , $i6:literal : $v6:expr ,
+        $i7:literal : | $($rest:tt)*
+    ) => {
+        $crate::arr!(impl $arr $mode { $i1 : $v1 });
+        $crate::arr!(impl $arr $mode { $i2 : $v2 });
+        $crate::arr!(impl $arr $mode { $i3 : $v3 });
+        $crate::arr!(impl $arr $mode { $i4 : $v4 });
+        $crate::arr!(impl $arr $mode { $i5 : $v5 });
+        $crate::arr!(impl $arr $mode { $i6 : $v6 });
+        $crate::arr!(impl $arr @munch $mode [$i7] : | $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt []
+        $i1:literal : $v1:expr , $i2:literal : $v2:expr , $i3:literal : $v3:expr , $i4:literal : $v4:expr , $i5:literal : $v5:expr , $i6:literal : $v6:expr , $i7:literal : $v7:expr ,
+        $i8:literal : | $($rest:tt)*
+    ) => {
+        $crate::arr!(impl $arr $mode { $i1 : $v1 });
+        $crate::arr!(impl $arr $mode { $i2 : $v2 });
+        $crate::arr!(impl $arr $mode { $i3 : $v3 });
+        $crate::arr!(impl $arr $mode { $i4 : $v4 });
+        $crate::arr!(impl $arr $mode { $i5 : $v5 });
+        $crate::arr!(impl $arr $mode { $i6 : $v6 });
+        $crate::arr!(impl $arr $mode { $i7 : $v7 });
+        $crate::arr!(impl $arr @munch $mode [$i8] : | $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt []
+        $i1:literal : $v1:expr , $i2:literal : $v2:expr , $i3:literal : $v3:expr , $i4:literal : $v4:expr , $i5:literal : $v5:expr , $i6:literal : $v6:expr , $i7:literal : $v7:expr , $i8:literal : $v8:expr
+        $(, $($rest:tt)*)?
+    ) => {
+        $crate::arr!(impl $arr $mode { $i1 : $v1 });
+        $crate::arr!(impl $arr $mode { $i2 : $v2 });
+        $crate::arr!(impl $arr $mode { $i3 : $v3 });
+        $crate::arr!(impl $arr $mode { $i4 : $v4 });
+        $crate::arr!(impl $arr $mode { $i5 : $v5 });
+        $crate::arr!(impl $arr $mode { $i6 : $v6 });
+        $crate::arr!(impl $arr $mode { $i7 : $v7 });
+        $crate::arr!(impl $arr $mode { $i8 : $v8 });
+        $crate::arr!(impl $arr @munch $mode [] $($($rest)*)?);
+    };
+    // a string literal before an entry is a label, which is ignored. It must
+    // be followed by a token that can't come after a literal index
+    (impl $arr:ident @munch $mode:tt [] $label:literal [ $($next:tt)* ] $($rest:tt)*) => {
+        $crate::arr!(impl $arr @label $mode $label [[ $($next)* ]] $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt [] $label:literal ( $($next:tt)* ) $($rest:tt)*) => {
+        $crate::arr!(impl $arr @label $mode $label [( $($next)* )] $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt [] $label:literal ^ $($rest:tt)*) => {
+        $crate::arr!(impl $arr @label $mode $label [^] $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt [] $label:literal $next:ident $($rest:tt)*) => {
+        $crate::arr!(impl $arr @label $mode $label [$next] $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt [] $label:literal $next:literal : $($rest:tt)*) => {
+        $crate::arr!(impl $arr @label $mode $label [$next :] $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt [] $label:literal $next:literal .. $($rest:tt)*) => {
+        $crate::arr!(impl $arr @label $mode $label [$next ..] $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt [] $label:literal $next:literal ..= $($rest:tt)*) => {
+        $crate::arr!(impl $arr @label $mode $label [$next ..=] $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt [] $label:literal $next:literal | $($rest:tt)*) => {
+        $crate::arr!(impl $arr @label $mode $label [$next |] $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt [] base $base:tt { $($inner:tt)* } $(, $($rest:tt)*)?) => {
+        {
+            let group = &mut $arr[$base..];
+            $crate::arr!(impl group @block { $($inner)* });
+        }
+        $crate::arr!(impl $arr @munch $mode [] $($($rest)*)?);
+    };
+    (impl $arr:ident @munch $mode:tt [] from_pairs $pairs:expr $(, $($rest:tt)*)?) => {
+        for (i, value) in $pairs {
+            $crate::arr!(impl @bounds $mode $arr i);
+            $arr[i] = value;
+        }
+        $crate::arr!(impl $arr @munch $mode [] $($($rest)*)?);
+    };
+    // removals must be at the end of the block, so they are applied after all
+    // other entries
+    (impl $arr:ident @munch $mode:tt [] $(remove $index:expr),+ $(,)?) => {
+        $crate::__remove_indices(&mut $arr, [$($index),+]);
+    };
+    (impl $arr:ident @munch $mode:tt [] remove $($rest:tt)*) => {
+        $crate::__error!(remove_not_last);
+    };
+    (impl $arr:ident @munch $mode:tt [] mirror $(, $($rest:tt)*)?) => {
+        $crate::arr!(impl @mirror $mode $arr);
+        $crate::arr!(impl $arr @munch $mode [] $($($rest)*)?);
+    };
+    // the bounds of the range are bound to variables, so the entry is handled
+    // like a literal range
+    (impl $arr:ident @munch $mode:tt [] range $range:tt : $($rest:tt)*) => {
+        $crate::arr!(impl $arr @range $mode $range [:] $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt [] range $range:tt where $($rest:tt)*) => {
+        $crate::arr!(impl $arr @range $mode $range [where] $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt [] range $range:tt if $($rest:tt)*) => {
+        $crate::arr!(impl $arr @range $mode $range [if] $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt [$($index:tt)+] : [ $($value:tt)* ] * $count:tt $(, $($rest:tt)*)?) => {
+        $crate::arr!(impl @key $arr $mode [] [] ($($index)+) { [ $($value)* ] * $count });
+        $crate::arr!(impl $arr @munch $mode [] $($($rest)*)?);
+    };
+    (impl $arr:ident @munch $mode:tt [$($index:tt)+] : [ $($value:tt)* ] $(, $($rest:tt)*)?) => {
+        $crate::arr!(impl @key $arr $mode [] [] ($($index)+) { [ $($value)* ] });
+        $crate::arr!(impl $arr @munch $mode [] $($($rest)*)?);
+    };
+    (impl $arr:ident @munch $mode:tt [$($index:tt)+] : $src:tt [ $($range:tt)* ] of $len:tt $(, $($rest:tt)*)?) => {
+        $crate::arr!(impl @key $arr $mode [] [] ($($index)+) { $src [ $($range)* ] of $len });
+        $crate::arr!(impl $arr @munch $mode [] $($($rest)*)?);
+    };
+    (impl $arr:ident @munch $mode:tt [$($index:tt)+] : from $src:tt $(, $($rest:tt)*)?) => {
+        $crate::arr!(impl @key $arr $mode [] [] ($($index)+) { from $src });
+        $crate::arr!(impl $arr @munch $mode [] $($($rest)*)?);
+    };
+    // compound assignments modify the existing element
+    (impl $arr:ident @munch $mode:tt [$($index:tt)+] : += $($rest:tt)*) => {
+        $crate::arr!(impl $arr @munch $mode [$($index)+] @op (+=) $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt [$($index:tt)+] : -= $($rest:tt)*) => {
+        $crate::arr!(impl $arr @munch $mode [$($index)+] @op (-=) $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt [$($index:tt)+] : *= $($rest:tt)*) => {
+        $crate::arr!(impl $arr @munch $mode [$($index)+] @op (*=) $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt [$($index:tt)+] : /= $($rest:tt)*) => {
+        $crate::arr!(impl $arr @munch $mode [$($index)+] @op (/=) $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt [$($index:tt)+] : %= $($rest:tt)*) => {
+        $crate::arr!(impl $arr @munch $mode [$($index)+] @op (%=) $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt [$($index:tt)+] : &= $($rest:tt)*) => {
+        $crate::arr!(impl $arr @munch $mode [$($index)+] @op (&=) $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt [$($index:tt)+] : |= $($rest:tt)*) => {
+        $crate::arr!(impl $arr @munch $mode [$($index)+] @op (|=) $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt [$($index:tt)+] : ^= $($rest:tt)*) => {
+        $crate::arr!(impl $arr @munch $mode [$($index)+] @op (^=) $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt [$($index:tt)+] : <<= $($rest:tt)*) => {
+        $crate::arr!(impl $arr @munch $mode [$($index)+] @op (<<=) $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt [$($index:tt)+] : >>= $($rest:tt)*) => {
+        $crate::arr!(impl $arr @munch $mode [$($index)+] @op (>>=) $($rest)*);
+    };
+    // in `const` items, the mode is kept, so indices from the end can be
+    // checked without formatting the message
+    (impl $arr:ident @munch [@const] [$($index:tt)+] @op ($op:tt)
+        | $a:ident | $value:expr $(, $($rest:tt)*)?
+    ) => {
+        let value = {
+            let $a = &$arr;
+            $value
+        };
+        $crate::arr!(impl @key $arr [@op $op @const] [] [] ($($index)+) { value });
+        $crate::arr!(impl $arr @munch [@const] [] $($($rest)*)?);
+    };
+    (impl $arr:ident @munch [@const] [$($index:tt)+] @op ($op:tt) $value:expr $(, $($rest:tt)*)?) => {
+        $crate::arr!(impl @key $arr [@op $op @const] [] [] ($($index)+) { $value });
+        $crate::arr!(impl $arr @munch [@const] [] $($($rest)*)?);
+    };
+    (impl $arr:ident @munch $mode:tt [$($index:tt)+] @op ($op:tt)
+        | $a:ident | $value:expr $(, $($rest:tt)*)?
+    ) => {
+        let value = {
+            let $a = &$arr;
+            $value
+        };
+        $crate::arr!(impl @key $arr [@op $op] [] [] ($($index)+) { value });
+        $crate::arr!(impl $arr @munch $mode [] $($($rest)*)?);
+    };
+    (impl $arr:ident @munch $mode:tt [$($index:tt)+] @op ($op:tt) $value:expr $(, $($rest:tt)*)?) => {
+        $crate::arr!(impl @key $arr [@op $op] [] [] ($($index)+) { $value });
+        $crate::arr!(impl $arr @munch $mode [] $($($rest)*)?);
+    };
+    // the value is computed from each index by calling a closure
+    (impl $arr:ident @munch $mode:tt [$($index:tt)+] : fn $f:expr $(, $($rest:tt)*)?) => {
+        $crate::arr!(impl @key $arr [@fn] [] [] ($($index)+) { $f });
+        $crate::arr!(impl $arr @munch $mode [] $($($rest)*)?);
+    };
+    // the value is computed from the previous element and the index
+    (impl $arr:ident @munch $mode:tt [$($index:tt)+]
+        : | $prev:tt , $i:tt | $value:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::arr!(impl @prev $mode $arr [$($index)+] | $prev , $i | $value);
+        $crate::arr!(impl $arr @munch $mode [] $($($rest)*)?);
+    };
+    // the value is computed from the array before it is assigned. It is moved
+    // into a single index, and cloned for each index of a range or `|`
+    (impl $arr:ident @munch $mode:tt [$index:literal] : | $a:ident | $value:expr $(, $($rest:tt)*)?) => {
+        let value = {
+            let $a = &$arr;
+            $value
+        };
+        $crate::arr!(impl @key $arr $mode [] [] ($index) { value });
+        $crate::arr!(impl $arr @munch $mode [] $($($rest)*)?);
+    };
+    (impl $arr:ident @munch $mode:tt [$index:ident] : | $a:ident | $value:expr $(, $($rest:tt)*)?) => {
+        let value = {
+            let $a = &$arr;
+            $value
+        };
+        $crate::arr!(impl @key $arr $mode [] [] ($index) { value });
+        $crate::arr!(impl $arr @munch $mode [] $($($rest)*)?);
+    };
+    // values in `const` items are `Copy`, but can't be cloned
+    (impl $arr:ident @munch [@const] [$($index:tt)+] : | $a:ident | $value:expr $(, $($rest:tt)*)?) => {
+        let value = {
+            let $a = &$arr;
+            $value
+        };
+        $crate::arr!(impl @key $arr [@const] [] [] ($($index)+) { value });
+        $crate::arr!(impl $arr @munch [@const] [] $($($rest)*)?);
+    };
+    (impl $arr:ident @munch $mode:tt [$($index:tt)+] : | $a:ident | $value:expr $(, $($rest:tt)*)?) => {
+        let value = {
+            let $a = &$arr;
+            $value
+        };
+        $crate::arr!(impl @key $arr $mode [] [] ($($index)+) { ::core::clone::Clone::clone(&value) });
+        $crate::arr!(impl $arr @munch $mode [] $($($rest)*)?);
+    };
+    (impl $arr:ident @munch $mode:tt [$($index:tt)+] : $value:expr $(, $($rest:tt)*)?) => {
+        $crate::arr!(impl @key $arr $mode [] [] ($($index)+) { $value });
+        $crate::arr!(impl $arr @munch $mode [] $($($rest)*)?);
+    };
+    (impl $arr:ident @munch $mode:tt [$($index:tt)+] => $($rest:tt)*) => {
+        $crate::__error!(fat_arrow [$($index)+]);
+    };
+    (impl $arr:ident @munch $mode:tt [$($index:tt)+] $(, $($rest:tt)*)?) => {
+        $crate::__error!(missing_colon [$($index)+]);
+    };
+    // the entry is only applied to indices for which the guard returns true,
+    // or with `if`, to elements for which it returns true
+    (impl $arr:ident @munch $mode:tt [$($index:tt)+] where $($rest:tt)*) => {
+        $crate::arr!(impl $arr @where $mode [$($index)+] [where] $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt [$($index:tt)+] if $($rest:tt)*) => {
+        $crate::arr!(impl $arr @where $mode [$($index)+] [if] $($rest)*);
+    };
+    (impl $arr:ident @munch $mode:tt [$($index:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::arr!(impl $arr @munch $mode [$($index)* $next] $($rest)*);
+    };
+    (impl $arr:ident @label $mode:tt $label:literal [$($next:tt)+] $($rest:tt)*) => {
+        let _: &str = $label;
+        $crate::arr!(impl $arr @munch $mode [] $($next)+ $($rest)*);
+    };
+    (impl $arr:ident @range $mode:tt $range:tt [$sep:tt] $($rest:tt)*) => {
+        let range: ::core::ops::Range<usize> = $range;
+        let range_start = range.start;
+        let range_end = range.end;
+        $crate::arr!(impl $arr @munch $mode [range_start .. range_end] $sep $($rest)*);
+    };
+    (impl $arr:ident @where $mode:tt [$($index:tt)+] [$($guard:tt)+]
+        : fn $f:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::arr!(impl @key $arr [@fn ($($guard)+)] [] [] ($($index)+) { $f });
+        $crate::arr!(impl $arr @munch $mode [] $($($rest)*)?);
+    };
+    (impl $arr:ident @where $mode:tt [$($index:tt)+] [$($guard:tt)+]
+        : | $a:ident | $value:expr $(, $($rest:tt)*)?
+    ) => {
+        let value = {
+            let $a = &$arr;
+            $value
+        };
+        $crate::arr!(impl @key $arr [@fn ($($guard)+)] [] [] ($($index)+) { |_| ::core::clone::Clone::clone(&value) });
+        $crate::arr!(impl $arr @munch $mode [] $($($rest)*)?);
+    };
+    (impl $arr:ident @where $mode:tt [$($index:tt)+] [$($guard:tt)+]
+        : $value:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::arr!(impl @key $arr [@fn ($($guard)+)] [] [] ($($index)+) { |_| $value });
+        $crate::arr!(impl $arr @munch $mode [] $($($rest)*)?);
+    };
+    (impl $arr:ident @where $mode:tt [$($index:tt)+] [$($guard:tt)*] $(, $($rest:tt)*)?) => {
+        $crate::__error!(missing_guard_colon [$($guard)*]);
+    };
+    (impl $arr:ident @where $mode:tt [$($index:tt)+] [$($guard:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::arr!(impl $arr @where $mode [$($index)+] [$($guard)* $next] $($rest)*);
+    };
+    (impl $arr:ident @attr $mode:tt [$($attr:tt)*] [$($entry:tt)*] $(, $($rest:tt)*)?) => {
+        #[$($attr)*]
+        {
+            $crate::arr!(impl $arr @munch $mode [] $($entry)*);
+        }
+        $crate::arr!(impl $arr @munch $mode [] $($($rest)*)?);
+    };
+    (impl $arr:ident @attr $mode:tt $attr:tt [$($entry:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::arr!(impl $arr @attr $mode $attr [$($entry)* $next] $($rest)*);
+    };
+    // sets a field of an element, e.g. `3 .1` or `(i).x`. An identifier
+    // followed by a field is an index expression, so `pos.0` is still the
+    // index `pos.0`
+    (impl @key $arr:ident $mode:tt [] [] ($index:literal $(. $field:tt)+) { $($value:tt)* }) => {
+        $crate::arr!(impl $arr $mode { @field ($index) [$($field)+] : $($value)* });
+    };
+    (impl @key $arr:ident $mode:tt [] [] (( $($index:tt)+ ) $(. $field:tt)+) { $($value:tt)* }) => {
+        $crate::arr!(impl $arr $mode { @field ($($index)+) [$($field)+] : $($value)* });
+    };
+    // wraps every part of an index that consists of several tokens in
+    // parentheses, so `Color::Red as usize` can be matched as a single `tt`.
+    // The parts are separated by `..`, `..=`, `|` and `;`
+    (impl @key $arr:ident $mode:tt [] [] (^ $($index:tt)+) { $($value:tt)* }) => {
+        $crate::arr!(impl @key $arr $mode [^] [] ($($index)+) { $($value)* });
+    };
+    (impl @key $arr:ident $mode:tt [] [] (rev $($index:tt)+) { $($value:tt)* }) => {
+        $crate::arr!(impl @key $arr $mode [rev] [] ($($index)+) { $($value)* });
+    };
+    (impl @key $arr:ident $mode:tt [] [] (clamp $($index:tt)+) { $($value:tt)* }) => {
+        $crate::arr!(impl @key $arr $mode [clamp] [] ($($index)+) { $($value)* });
+    };
+    (impl @key $arr:ident $mode:tt [] [] (wrap $($index:tt)+) { $($value:tt)* }) => {
+        $crate::arr!(impl @key $arr $mode [wrap] [] ($($index)+) { $($value)* });
+    };
+    (impl @key $arr:ident $mode:tt [] [] (in $($index:tt)+) { $($value:tt)* }) => {
+        $crate::arr!(impl $arr $mode { in ($($index)+) : $($value)* });
+    };
+    (impl @key $arr:ident $mode:tt [$($done:tt)*] [$($part:tt)*] (..= $($index:tt)*) $value:tt) => {
+        $crate::arr!(impl @key $arr $mode [$($done)*] [$($part)*] @sep (..=) ($($index)*) $value);
+    };
+    (impl @key $arr:ident $mode:tt [$($done:tt)*] [$($part:tt)*] (.. $($index:tt)*) $value:tt) => {
+        $crate::arr!(impl @key $arr $mode [$($done)*] [$($part)*] @sep (..) ($($index)*) $value);
+    };
+    (impl @key $arr:ident $mode:tt [$($done:tt)*] [$($part:tt)*] (| $($index:tt)*) $value:tt) => {
+        $crate::arr!(impl @key $arr $mode [$($done)*] [$($part)*] @sep (|) ($($index)*) $value);
+    };
+    (impl @key $arr:ident $mode:tt [$($done:tt)*] [$($part:tt)*] (; $($index:tt)*) $value:tt) => {
+        $crate::arr!(impl @key $arr $mode [$($done)*] [$($part)*] @sep (;) ($($index)*) $value);
+    };
+    (impl @key $arr:ident $mode:tt [$($done:tt)*] [] @sep ($sep:tt) $($rest:tt)*) => {
+        $crate::arr!(impl @key $arr $mode [$($done)* $sep] [] $($rest)*);
+    };
+    (impl @key $arr:ident $mode:tt [$($done:tt)*] [$part:tt] @sep ($sep:tt) $($rest:tt)*) => {
+        $crate::arr!(impl @key $arr $mode [$($done)* $part $sep] [] $($rest)*);
+    };
+    (impl @key $arr:ident $mode:tt [$($done:tt)*] [$($part:tt)+] @sep ($sep:tt) $($rest:tt)*) => {
+        $crate::arr!(impl @key $arr $mode [$($done)* ($($part)+) $sep] [] $($rest)*);
+    };
+    (impl @key $arr:ident $mode:tt [$($done:tt)*] [] () { $($value:tt)* }) => {
+        $crate::arr!(impl $arr $mode { $($done)* : $($value)* });
+    };
+    (impl @key $arr:ident $mode:tt [$($done:tt)*] [$part:tt] () { $($value:tt)* }) => {
+        $crate::arr!(impl $arr $mode { $($done)* $part : $($value)* });
+    };
+    (impl @key $arr:ident $mode:tt [$($done:tt)*] [$($part:tt)+] () { $($value:tt)* }) => {
+        $crate::arr!(impl $arr $mode { $($done)* ($($part)+) : $($value)* });
+    };
+    (impl @key $arr:ident $mode:tt [$($done:tt)*] [$($part:tt)*] ($next:tt $($index:tt)*) $value:tt) => {
+        $crate::arr!(impl @key $arr $mode [$($done)*] [$($part)* $next] ($($index)*) $value);
+    };
+    (impl $arr:ident [@op $op:tt @const] { ^ $offset:tt : $value:expr }) => {
+        let offset = $offset;
+        let len = $arr.len();
+        assert!(offset < len, "array-lit: index from the end is out of bounds");
+        $arr[len - 1 - offset] $op $value;
+    };
+    (impl $arr:ident [@op $op:tt @const] { $($entry:tt)* }) => {
+        $crate::arr!(impl $arr [@op $op] { $($entry)* });
+    };
+    (impl $arr:ident [@op $op:tt] { @field $index:tt [$($field:tt)+] : $value:expr }) => {
+        $arr[$crate::__index!($index)] $(.$field)+ $op $value;
+    };
+    (impl $arr:ident [@auto $item:ident] { @field $index:tt $fields:tt : $value:expr }) => {
+        $crate::arr!(impl @grow $arr $item ($crate::__index!($index) + 1));
+        $crate::arr!(impl $arr [] { @field $index $fields : $value });
+    };
+    (impl $arr:ident [@fn $($guard:tt)?] { @field $index:tt [$($field:tt)+] : $($value:tt)* }) => {
+        $crate::__error!(unsupported fn [$index $(. $field)+]);
+    };
+    (impl $arr:ident $mode:tt { @field $index:tt [$($field:tt)+] : $value:expr }) => {
+        let value = $value;
+        let index = $crate::__index!($index);
+        $crate::arr!(impl @bounds $mode $arr index);
+        $arr[index] $(.$field)+ = value;
+    };
+    // a block with several start indices is inserted once at every start
+    (impl $arr:ident $mode:tt { [$first:tt , $($rest:tt)+] : $($value:tt)* }) => {
+        $crate::arr!(impl $arr $mode { [$first] : $($value)* });
+        $crate::arr!(impl $arr $mode { [$($rest)+] : $($value)* });
+    };
+    (impl $arr:ident $mode:tt { [$first:tt ,] : $($value:tt)* }) => {
+        $crate::arr!(impl $arr $mode { [$first] : $($value)* });
+    };
+    (impl $arr:ident [@op $op:tt] { in $indices:tt : $value:expr }) => {
+        for i in $indices {
+            $arr[i] $op $value;
+        }
+    };
+    (impl $arr:ident [@op $op:tt] { ^ $offset:tt : $value:expr }) => {
+        let len = $arr.len();
+        let index = {
+            #[allow(unused_imports)]
+            use $crate::__IndexFromEnd;
+            $arr.index_from_end(len, $offset)
+        };
+        $arr[index] $op $value;
+    };
+    (impl $arr:ident [@op $op:tt] { .. $end:tt : $value:expr }) => {
+        $crate::arr!(impl [@op $op] $arr (0) (.. $end) $value);
+    };
+    (impl $arr:ident [@op $op:tt] { ..= $end:tt : $value:expr }) => {
+        $crate::arr!(impl [@op $op] $arr (0) (.. $end + 1) $value);
+    };
+    (impl $arr:ident [@op $op:tt] { $start:tt .. : $value:expr }) => {
+        let end = $arr.len();
+        $crate::arr!(impl [@op $op] $arr ($start) (.. end) $value);
+    };
+    (impl $arr:ident [@op $op:tt] { $start:tt .. $end:tt : $value:expr }) => {
+        $crate::arr!(impl [@op $op] $arr ($start) (.. $end) $value);
+    };
+    (impl $arr:ident [@op $op:tt] { $start:tt ..= $end:tt : $value:expr }) => {
+        $crate::arr!(impl [@op $op] $arr ($start) (.. $end + 1) $value);
+    };
+    (impl $arr:ident [@op $op:tt] { $first:tt $(| $key:tt)+ : $value:expr }) => {
+        let value = $value;
+        $arr[$crate::__index!($first)] $op value;
+        $( $arr[$crate::__index!($key)] $op value; )+
+    };
+    (impl $arr:ident [@op $op:tt] { $key:tt : $value:expr }) => {
+        $arr[$crate::__index!($key)] $op $value;
+    };
+    (impl $arr:ident [@op $op:tt] { $($entry:tt)* }) => {
+        $crate::__error!(unsupported $op [$($entry)*]);
+    };
+    (impl [@op $op:tt] $arr:ident ($start:expr) (.. $end:expr) $value:expr) => {
+        let mut i = $start;
+        let end = $end;
+        while i < end {
+            $arr[i] $op $value;
+            i += 1;
+        }
+    };
+    (impl $arr:ident [@fn $($guard:tt)?] { in $indices:tt : $f:expr }) => {
+        $crate::arr!(impl [@fn $($guard)?] $arr ($indices) $f);
+    };
+    (impl $arr:ident [@fn $($guard:tt)?] { rev .. $end:tt : $f:expr }) => {
+        $crate::arr!(impl [@fn $($guard)?] $arr ((0..$end).rev()) $f);
+    };
+    (impl $arr:ident [@fn $($guard:tt)?] { rev ..= $end:tt : $f:expr }) => {
+        $crate::arr!(impl [@fn $($guard)?] $arr ((0..=$end).rev()) $f);
+    };
+    (impl $arr:ident [@fn $($guard:tt)?] { rev $start:tt .. $end:tt ; $step:tt : $f:expr }) => {
+        let step = $step;
+        assert!(step != 0, "array-lit: step of a range index must not be zero");
+        $crate::arr!(impl [@fn $($guard)?] $arr (($start..$end).step_by(step).rev()) $f);
+    };
+    (impl $arr:ident [@fn $($guard:tt)?] { rev $start:tt .. $end:tt : $f:expr }) => {
+        $crate::arr!(impl [@fn $($guard)?] $arr (($start..$end).rev()) $f);
+    };
+    (impl $arr:ident [@fn $($guard:tt)?] { rev $start:tt ..= $end:tt : $f:expr }) => {
+        $crate::arr!(impl [@fn $($guard)?] $arr (($start..=$end).rev()) $f);
+    };
+    (impl $arr:ident [@fn $($guard:tt)?] { .. $end:tt : $f:expr }) => {
+        $crate::arr!(impl [@fn $($guard)?] $arr (0..$end) $f);
+    };
+    (impl $arr:ident [@fn $($guard:tt)?] { ..= $end:tt : $f:expr }) => {
+        $crate::arr!(impl [@fn $($guard)?] $arr (0..=$end) $f);
+    };
+    (impl $arr:ident [@fn $($guard:tt)?] { $start:tt .. : $f:expr }) => {
+        $crate::arr!(impl [@fn $($guard)?] $arr ($start..$arr.len()) $f);
+    };
+    (impl $arr:ident [@fn $($guard:tt)?] { $start:tt .. $end:tt ; $step:tt : $f:expr }) => {
+        let step = $step;
+        assert!(step != 0, "array-lit: step of a range index must not be zero");
+        $crate::arr!(impl [@fn $($guard)?] $arr (($start..$end).step_by(step)) $f);
+    };
+    (impl $arr:ident [@fn $($guard:tt)?] { $start:tt .. $end:tt : $f:expr }) => {
+        $crate::arr!(impl [@fn $($guard)?] $arr ($start..$end) $f);
+    };
+    (impl $arr:ident [@fn $($guard:tt)?] { $start:tt ..= $end:tt : $f:expr }) => {
+        $crate::arr!(impl [@fn $($guard)?] $arr ($start..=$end) $f);
+    };
+    (impl $arr:ident [@fn $($guard:tt)?] { $first:tt $(| $key:tt)+ : $f:expr }) => {
+        $crate::arr!(impl [@fn $($guard)?] $arr ([$crate::__index!($first) $(, $crate::__index!($key))+]) $f);
+    };
+    (impl $arr:ident [@fn $($guard:tt)?] { $key:tt : $f:expr }) => {
+        $crate::arr!(impl [@fn $($guard)?] $arr ([$crate::__index!($key)]) $f);
+    };
+    (impl $arr:ident [@fn ($kw:tt $($guard:tt)*)] { $($entry:tt)* }) => {
+        $crate::__error!(unsupported $kw [$($entry)*]);
+    };
+    (impl $arr:ident [@fn] { $($entry:tt)* }) => {
+        $crate::__error!(unsupported fn [$($entry)*]);
+    };
+    (impl [@fn (where $($guard:tt)*)] $arr:ident ($indices:expr) $f:expr) => {
+        let mut f = $crate::__index_fn($f);
+        let mut guard = $crate::__index_guard($($guard)*);
+        for i in $indices {
+            $crate::arr!(impl @bounds [] $arr i);
+            if guard(i) {
+                $arr[i] = f(i);
+            }
+        }
+    };
+    // the element is only borrowed while the guard is called
+    (impl [@fn (if $($guard:tt)*)] $arr:ident ($indices:expr) $f:expr) => {
+        let mut f = $crate::__index_fn($f);
+        let mut guard = $crate::__element_guard(&$arr, $($guard)*);
+        for i in $indices {
+            $crate::arr!(impl @bounds [] $arr i);
+            if guard(&$arr[i]) {
+                $arr[i] = f(i);
+            }
+        }
+    };
+    (impl [@fn] $arr:ident ($indices:expr) $f:expr) => {
+        let mut f = $crate::__index_fn($f);
+        for i in $indices {
+            $crate::arr!(impl @bounds [] $arr i);
+            $arr[i] = f(i);
+        }
+    };
+    (impl $arr:ident [] { $($entry:tt)* }) => {
+        $crate::arr!(impl $arr { $($entry)* });
+    };
+
+    // in const contexts, slices are copied with a loop instead of
+    // `copy_from_slice`, and panics can't format their message
+    (impl $arr:ident [@const] { [$start:tt ..] : [ $value:expr ; $len:expr ] }) => {
+        let start = $start;
+        let end = start + $len;
+        let len = $arr.len();
+        $crate::arr!(impl @loop [@const] $arr (start) (.. if end < len { end } else { len }) (1) $value);
+    };
+    (impl $arr:ident [@const] { [$start:tt ..] : [ $($value:tt)* ] }) => {
+        $crate::arr!(impl $arr { [$start ..] : [ $($value)* ] });
+    };
+    (impl $arr:ident [@const] { [$start:tt ..] : $value:expr }) => {
+        let mut i = $start;
+        let start = i;
+        let arr_inner = $value;
+        let end = i + arr_inner.len();
+        while i < end && i < $arr.len() {
+            $arr[i] = arr_inner[i - start];
+            i += 1;
+        }
+    };
+    (impl $arr:ident [@const] { [$start:tt] : [ $($value:expr),* $(,)? ] * $count:tt }) => {
+        $crate::arr!(impl @repeat [@const] $arr ($start) ($count) [ $($value),* ]);
+    };
+    (impl $arr:ident [@const] { [$start:tt] : [ $value:expr ; $len:expr ] }) => {
+        $arr = $crate::__fill_const($arr, $start, $len, $value);
+    };
+    (impl $arr:ident [@const] { [$start:tt] : [ $($value:tt)* ] }) => {
+        $crate::arr!(impl $arr { [$start] : [ $($value)* ] });
+    };
+    (impl $arr:ident [@const] { [$start:tt] : $value:expr }) => {
+        let mut i = $start;
+        let start = i;
+        let arr_inner = $value;
+        let end = i + arr_inner.len();
+        while i < end {
+            $arr[i] = arr_inner[i - start];
+            i += 1;
+        }
+    };
+    (impl $arr:ident [@const] { $start:tt .. $end:tt : from $src:tt }) => {
+        let start = $start;
+        let src = $src;
+        let len = $end - start;
+        if src < start {
+            let mut i = len;
+            while i > 0 {
+                i -= 1;
+                $arr[start + i] = $arr[src + i];
+            }
+        } else {
+            let mut i = 0;
+            while i < len {
+                $arr[start + i] = $arr[src + i];
+                i += 1;
+            }
+        }
+    };
+    (impl $arr:ident [@const] { ^ $offset:tt : $value:expr }) => {
+        let offset = $offset;
+        let len = $arr.len();
+        assert!(offset < len, "array-lit: index from the end is out of bounds");
+        $arr[len - 1 - offset] = $value;
+    };
+    (impl $arr:ident [@const] { .. $end:tt : $value:expr }) => {
+        $crate::arr!(impl @loop [@const] $arr (0) (.. $end) (1) $value);
+    };
+    (impl $arr:ident [@const] { ..= $end:tt : $value:expr }) => {
+        $crate::arr!(impl @loop [@const] $arr (0) (..= $end) (1) $value);
+    };
+    (impl $arr:ident [@const] { $start:tt .. : $value:expr }) => {
+        let start = $start;
+        let end = $arr.len();
+        $crate::arr!(impl @loop [@const] $arr (start) (.. end) (1) $value);
+    };
+    (impl $arr:ident [@const] { rev .. $end:tt : $value:expr }) => {
+        $crate::arr!(impl @rev [@const] $arr (0) (.. $end) (1) $value);
+    };
+    (impl $arr:ident [@const] { rev ..= $end:tt : $value:expr }) => {
+        $crate::arr!(impl @rev [@const] $arr (0) (..= $end) (1) $value);
+    };
+    (impl $arr:ident [@const] { rev $start:tt .. $end:tt ; $step:tt : $value:expr }) => {
+        $crate::arr!(impl @rev [@const] $arr ($start) (.. $end) ($step) $value);
+    };
+    (impl $arr:ident [@const] { rev $start:tt .. $end:tt : $value:expr }) => {
+        $crate::arr!(impl @rev [@const] $arr ($start) (.. $end) (1) $value);
+    };
+    (impl $arr:ident [@const] { rev $start:tt ..= $end:tt : $value:expr }) => {
+        $crate::arr!(impl @rev [@const] $arr ($start) (..= $end) (1) $value);
+    };
+    (impl $arr:ident [@const] { $start:tt .. $end:tt ; $step:tt : $value:expr }) => {
+        let start = $start;
+        let end = $end;
+        let step = $step;
+        assert!(step != 0, "array-lit: step of a range index must not be zero");
+        $crate::arr!(impl @loop [@const] $arr (start) (.. end) (step) $value);
+    };
+    (impl $arr:ident [@const] { $start:tt .. $end:tt : $value:expr }) => {
+        $crate::arr!(impl @loop [@const] $arr ($start) (.. $end) (1) $value);
+    };
+    (impl $arr:ident [@const] { $start:tt ..= $end:tt : $value:expr }) => {
+        $crate::arr!(impl @loop [@const] $arr ($start) (..= $end) (1) $value);
+    };
+    (impl $arr:ident [@const] { repeat : $pattern:expr }) => {
+        let pattern = $pattern;
+        let pattern_len = pattern.len();
+        let mut i = 0;
+        let end = $arr.len();
+        while i < end && pattern_len > 0 {
+            $arr[i] = pattern[i % pattern_len];
+            i += 1;
+        }
+    };
+    (impl $arr:ident [@const] { [$start:tt] : $src:tt [ $($range:tt)* ] of $len:tt }) => {
+        $crate::arr!(impl @sub [@const] $arr ($start) $src ($len) [$($range)*]);
+    };
+    (impl $arr:ident [@const] { in $indices:tt : $value:expr }) => {
+        let indices = $indices;
+        let mut k = 0;
+        while k < indices.len() {
+            $arr[indices[k]] = $value;
+            k += 1;
+        }
+    };
+    (impl $arr:ident [@const] { $first:tt $(| $key:tt)+ : $value:expr }) => {
+        let value = $value;
+        $arr[$crate::__index!($first)] = value;
+        $( $arr[$crate::__index!($key)] = value; )+
+    };
+    (impl $arr:ident [@const] { $key:tt : $value:expr }) => {
+        $arr[$crate::__index!($key)] = $value;
+    };
+    (impl $arr:ident [@const] { $($entry:tt)* }) => {
+        $crate::arr!(impl $arr { $($entry)* });
+    };
+
+    // when the length of a `Vec` is inferred, it grows to the end of every
+    // entry before the entry is assigned
+    (impl $arr:ident [@auto $item:ident] { [$start:tt] : [ $($value:expr),* $(,)? ] * $count:tt }) => {
+        $crate::arr!(impl @grow $arr $item ($start + $count * (0 $(+ $crate::arr!(impl @one $value))*)));
+        $crate::arr!(impl $arr { [$start] : [ $($value),* ] * $count });
+    };
+    (impl $arr:ident [@auto $item:ident] { [$start:tt] : [ $value:expr ; $len:expr ] }) => {
+        $crate::arr!(impl @grow $arr $item ($start + $len));
+        $crate::arr!(impl $arr { [$start] : [ $value ; $len ] });
+    };
+    (impl $arr:ident [@auto $item:ident] { [$start:tt] : [ $($value:expr),* $(,)? ] }) => {
+        $crate::arr!(impl @grow $arr $item ($start $(+ $crate::arr!(impl @one $value))*));
+        $crate::arr!(impl $arr { [$start] : [ $($value),* ] });
+    };
+    (impl $arr:ident [@auto $item:ident] { [$start:tt] : $src:tt [ $($range:tt)* ] of $len:tt }) => {
+        $crate::arr!(impl @grow $arr $item ($start + $len));
+        $crate::arr!(impl $arr { [$start] : $src [ $($range)* ] of $len });
+    };
+    (impl $arr:ident [@auto $item:ident] { [$start:tt] : $value:expr }) => {
+        let value = $value;
+        $crate::arr!(impl @grow $arr $item ($start + value.len()));
+        $crate::arr!(impl $arr { [$start] : value });
+    };
+    (impl $arr:ident [@auto $item:ident] { .. $end:tt : $value:expr }) => {
+        $crate::arr!(impl @grow $arr $item ($end));
+        $crate::arr!(impl $arr { .. $end : $value });
+    };
+    (impl $arr:ident [@auto $item:ident] { ..= $end:tt : $value:expr }) => {
+        $crate::arr!(impl @grow $arr $item ($end + 1));
+        $crate::arr!(impl $arr { ..= $end : $value });
+    };
+    (impl $arr:ident [@auto $item:ident] { $start:tt .. $end:tt : from $src:tt }) => {
+        $crate::arr!(impl @grow $arr $item ($end));
+        $crate::arr!(impl $arr { $start .. $end : from $src });
+    };
+    (impl $arr:ident [@auto $item:ident] { rev .. $end:tt : $value:expr }) => {
+        $crate::arr!(impl @grow $arr $item ($end));
+        $crate::arr!(impl $arr { rev .. $end : $value });
+    };
+    (impl $arr:ident [@auto $item:ident] { rev ..= $end:tt : $value:expr }) => {
+        $crate::arr!(impl @grow $arr $item ($end + 1));
+        $crate::arr!(impl $arr { rev ..= $end : $value });
+    };
+    (impl $arr:ident [@auto $item:ident] { rev $start:tt .. $end:tt $(; $step:tt)? : $value:expr }) => {
+        $crate::arr!(impl @grow $arr $item ($end));
+        $crate::arr!(impl $arr { rev $start .. $end $(; $step)? : $value });
+    };
+    (impl $arr:ident [@auto $item:ident] { rev $start:tt ..= $end:tt : $value:expr }) => {
+        $crate::arr!(impl @grow $arr $item ($end + 1));
+        $crate::arr!(impl $arr { rev $start ..= $end : $value });
+    };
+    (impl $arr:ident [@auto $item:ident] { $start:tt .. $end:tt ; $step:tt : $value:expr }) => {
+        $crate::arr!(impl @grow $arr $item ($end));
+        $crate::arr!(impl $arr { $start .. $end ; $step : $value });
+    };
+    (impl $arr:ident [@auto $item:ident] { $start:tt .. $end:tt : $value:expr }) => {
+        $crate::arr!(impl @grow $arr $item ($end));
+        $crate::arr!(impl $arr { $start .. $end : $value });
+    };
+    (impl $arr:ident [@auto $item:ident] { $start:tt ..= $end:tt : $value:expr }) => {
+        $crate::arr!(impl @grow $arr $item ($end + 1));
+        $crate::arr!(impl $arr { $start ..= $end : $value });
+    };
+    (impl $arr:ident [@auto $item:ident] { $first:tt $(| $key:tt)+ : $value:expr }) => {
+        $crate::arr!(impl @grow $arr $item ($crate::__index!($first) + 1));
+        $( $crate::arr!(impl @grow $arr $item ($crate::__index!($key) + 1)); )+
+        $crate::arr!(impl $arr { $first $(| $key)+ : $value });
+    };
+    (impl $arr:ident [@auto $item:ident] { $key:tt : $value:expr }) => {
+        $crate::arr!(impl @grow $arr $item ($crate::__index!($key) + 1));
+        $crate::arr!(impl $arr { $key : $value });
+    };
+    (impl $arr:ident [@auto $item:ident] { $($entry:tt)* }) => {
+        $crate::__error!(infer_len_entry [$($entry)*]);
+    };
+    (impl @grow $arr:ident $item:ident ($($end:tt)*)) => {
+        let end = $($end)*;
+        if $arr.len() < end {
+            $arr.resize(end, ::core::clone::Clone::clone(&$item));
+        }
+    };
+
+    // sets the elements from `start` to `end` with the given step. The value
+    // is evaluated again for every element
+    (impl @loop $mode:tt $arr:ident ($start:expr) (.. $end:expr) ($step:expr) $value:expr) => {
+        let mut i = $start;
+        let end = $end;
+        let step = $step;
+        while i < end {
+            $crate::arr!(impl @bounds $mode $arr i);
+            $arr[i] = $value;
+            i += step;
+        }
+    };
+    (impl @loop $mode:tt $arr:ident ($start:expr) (..= $end:expr) ($step:expr) $value:expr) => {
+        let mut i = $start;
+        let end = $end;
+        let step = $step;
+        while i <= end {
+            $crate::arr!(impl @bounds $mode $arr i);
+            $arr[i] = $value;
+            i += step;
+        }
+    };
+
+    // like `@loop`, but the elements are set in reverse order, beginning with
+    // the last element of the range. The index never goes below `start`
+    (impl @rev $mode:tt $arr:ident ($start:expr) (.. $end:expr) ($step:expr) $value:expr) => {
+        let start = $start;
+        let end = $end;
+        let step = $step;
+        assert!(step != 0, "array-lit: step of a range index must not be zero");
+        if start < end {
+            let mut i = start + (end - 1 - start) / step * step;
+            loop {
+                $crate::arr!(impl @bounds $mode $arr i);
+                $arr[i] = $value;
+                if i - start < step {
+                    break;
+                }
+                i -= step;
+            }
+        }
+    };
+    (impl @rev $mode:tt $arr:ident ($start:expr) (..= $end:expr) ($step:expr) $value:expr) => {
+        $crate::arr!(impl @rev $mode $arr ($start) (.. $end + 1) ($step) $value);
+    };
+
+    // inserts the values `count` times in a row. The values are evaluated
+    // again for every repetition
+    (impl @repeat $mode:tt $arr:ident ($start:expr) ($count:expr) [ $($value:expr),* ]) => {
+        let mut i = $start;
+        let count = $count;
+        let mut k = 0;
+        while k < count {
+            $(
+                $crate::arr!(impl @bounds $mode $arr i);
+                $arr[i] = $value;
+                i += 1;
+            )*
+            k += 1;
+        }
+    };
+
+    // assigns every index of a range to a value computed from a reference to
+    // the previous element and the index
+    (impl @prev $mode:tt $arr:ident [$start:tt ..] $($f:tt)*) => {
+        let end = $arr.len();
+        $crate::arr!(impl @prev $mode $arr ($start) (end) $($f)*);
+    };
+    (impl @prev $mode:tt $arr:ident [$start:tt .. $($end:tt)+] $($f:tt)*) => {
+        $crate::arr!(impl @prev $mode $arr ($start) ($($end)+) $($f)*);
+    };
+    (impl @prev $mode:tt $arr:ident [$start:tt ..= $($end:tt)+] $($f:tt)*) => {
+        $crate::arr!(impl @prev $mode $arr ($start) (($($end)+) + 1) $($f)*);
+    };
+    (impl @prev $mode:tt $arr:ident [$($index:tt)+] $($f:tt)*) => {
+        $crate::__error!(prev_not_range [$($index)+]);
+    };
+    (impl @prev $mode:tt $arr:ident ($start:expr) ($end:expr) | $prev:tt , $i:tt | $value:expr) => {
+        let mut i = $start;
+        let end = $end;
+        assert!(i > 0 || i >= end, "array-lit: index 0 has no previous element");
+        while i < end {
+            $crate::arr!(impl @bounds $mode $arr i);
+            let value = {
+                let $prev = &$arr[i - 1];
+                let $i = i;
+                $value
+            };
+            $arr[i] = value;
+            i += 1;
+        }
+    };
+    // copies the first half of the array into the second half in reverse
+    // order. In a `const` context, `for` loops and `clone` aren't available
+    (impl @mirror [@const] $arr:ident) => {
+        {
+            let len = $arr.len();
+            let mut i = 0;
+            while i < len / 2 {
+                $arr[len - 1 - i] = $arr[i];
+                i += 1;
+            }
+        }
+    };
+    (impl @mirror $mode:tt $arr:ident) => {
+        {
+            let len = $arr.len();
+            for i in 0..len / 2 {
+                $arr[len - 1 - i] = $arr[i].clone();
+            }
+        }
+    };
+    // in debug builds, `usize` indices of arrays, slices and `Vec`s are
+    // checked before they are assigned, to panic with a clearer message
+    (impl @bounds [] $arr:ident $index:ident) => {
+        #[cfg(debug_assertions)]
+        {
+            #[allow(unused_imports)]
+            use $crate::{__CheckIndex, __SkipCheckIndex};
+            (&$crate::__Bounds(&$arr, &$index)).check_index();
+        }
+    };
+    (impl @bounds [@const] $arr:ident $index:ident) => {};
+    (impl @sub [@const] $arr:ident $start:tt $src:tt $len:tt [..]) => {
+        $crate::arr!(impl @sub [@const] $arr $start $src $len (0) ($src.len()));
+    };
+    (impl @sub [@const] $arr:ident $start:tt $src:tt $len:tt [.. $end:tt]) => {
+        $crate::arr!(impl @sub [@const] $arr $start $src $len (0) ($end));
+    };
+    (impl @sub [@const] $arr:ident $start:tt $src:tt $len:tt [..= $end:tt]) => {
+        $crate::arr!(impl @sub [@const] $arr $start $src $len (0) ($end + 1));
+    };
+    (impl @sub [@const] $arr:ident $start:tt $src:tt $len:tt [$from:tt ..]) => {
+        $crate::arr!(impl @sub [@const] $arr $start $src $len ($from) ($src.len()));
+    };
+    (impl @sub [@const] $arr:ident $start:tt $src:tt $len:tt [$from:tt .. $end:tt]) => {
+        $crate::arr!(impl @sub [@const] $arr $start $src $len ($from) ($end));
+    };
+    (impl @sub [@const] $arr:ident $start:tt $src:tt $len:tt [$from:tt ..= $end:tt]) => {
+        $crate::arr!(impl @sub [@const] $arr $start $src $len ($from) ($end + 1));
+    };
+    (impl @sub [@const] $arr:ident ($start:expr) $src:tt ($len:expr) ($from:expr) ($end:expr)) => {
+        let start = $start;
+        let from = $from;
+        let src = &$src;
+        assert!(
+            $end - from == $len,
+            "array-lit: the source slice doesn't have the expected length",
+        );
+        let mut k = 0;
+        while k < $len {
+            $arr[start + k] = src[from + k];
+            k += 1;
+        }
+    };
+    (impl @bounds [@auto $item:ident] $arr:ident $index:ident) => {
+        $crate::arr!(impl @grow $arr $item ($index + 1));
+    };
+
+    (impl $arr:ident { in $indices:tt : $value:expr }) => {
+        for i in $indices {
+            $crate::arr!(impl @bounds [] $arr i);
+            $arr[i] = $value;
+        }
+    };
+    (impl $arr:ident { repeat : $pattern:expr }) => {
+        let pattern = $pattern;
+        $crate::repeat_pattern(&mut $arr, &pattern);
+    };
+    // ranges with literal bounds use a plain loop without a bounds check,
+    // like literal indices, so they work in `const` items
+    (impl $arr:ident { .. $end:literal : $value:expr }) => {
+        $crate::arr!(impl @loop [@const] $arr (0) (.. $end) (1) $value);
+    };
+    (impl $arr:ident { ..= $end:literal : $value:expr }) => {
+        $crate::arr!(impl @loop [@const] $arr (0) (..= $end) (1) $value);
+    };
+    (impl $arr:ident { $start:literal .. $end:literal : $value:expr }) => {
+        $crate::arr!(impl @loop [@const] $arr ($start) (.. $end) (1) $value);
+    };
+    (impl $arr:ident { $start:literal ..= $end:literal : $value:expr }) => {
+        $crate::arr!(impl @loop [@const] $arr ($start) (..= $end) (1) $value);
+    };
+    (impl $arr:ident { .. $end:tt : $value:expr }) => {
+        $crate::__fill!($arr (0) (.. $end) (1) $value);
+    };
+    (impl $arr:ident { ..= $end:tt : $value:expr }) => {
+        $crate::__fill!($arr (0) (..= $end) (1) $value);
+    };
+    (impl $arr:ident { $start:tt .. : $value:expr }) => {
+        let start = $start;
+        let end = $arr.len();
+        $crate::__fill!($arr (start) (.. end) (1) $value);
+    };
+    (impl $arr:ident { $start:tt .. $end:tt : from $src:tt }) => {
+        let start = $start;
+        let src = $src;
+        $arr.copy_within(src..src + ($end - start), start);
+    };
+    (impl $arr:ident { rev .. $end:tt : $value:expr }) => {
+        $crate::arr!(impl @rev [] $arr (0) (.. $end) (1) $value);
+    };
+    (impl $arr:ident { rev ..= $end:tt : $value:expr }) => {
+        $crate::arr!(impl @rev [] $arr (0) (..= $end) (1) $value);
+    };
+    (impl $arr:ident { rev $start:tt .. $end:tt ; $step:tt : $value:expr }) => {
+        $crate::arr!(impl @rev [] $arr ($start) (.. $end) ($step) $value);
+    };
+    (impl $arr:ident { rev $start:tt .. $end:tt : $value:expr }) => {
+        $crate::arr!(impl @rev [] $arr ($start) (.. $end) (1) $value);
+    };
+    (impl $arr:ident { rev $start:tt ..= $end:tt : $value:expr }) => {
+        $crate::arr!(impl @rev [] $arr ($start) (..= $end) (1) $value);
+    };
+    (impl $arr:ident { $start:tt .. $end:tt ; $step:tt : $value:expr }) => {
+        let start = $start;
+        let end = $end;
+        let step = $step;
+        assert!(step != 0, "array-lit: step of a range index must not be zero");
+        $crate::__fill!($arr (start) (.. end) (step) $value);
+    };
+    (impl $arr:ident { $start:tt .. $end:tt : $value:expr }) => {
+        $crate::__fill!($arr ($start) (.. $end) (1) $value);
+    };
+    (impl $arr:ident { $start:tt ..= $end:tt : $value:expr }) => {
+        $crate::__fill!($arr ($start) (..= $end) (1) $value);
+    };
+    // indices past the end are clamped to the last element. Nothing is
+    // assigned if the array is empty
+    (impl $arr:ident { clamp $key:tt : $value:expr }) => {
+        let len = $arr.len();
+        if len > 0 {
+            let index = $key;
+            $arr[if index < len { index } else { len - 1 }] = $value;
+        }
+    };
+    // indices past the end wrap around to the start, like in a ring buffer
+    (impl $arr:ident { wrap $key:tt : $value:expr }) => {
+        let len = $arr.len();
+        if len > 0 {
+            $arr[$key % len] = $value;
+        }
+    };
+    (impl $arr:ident { ^ $offset:tt : $value:expr }) => {
+        let len = $arr.len();
+        let index = {
+            #[allow(unused_imports)]
+            use $crate::__IndexFromEnd;
+            $arr.index_from_end(len, $offset)
+        };
+        $arr[index] = $value;
+    };
+    // `[start..]` works like `[start]`, but stops at the end of the array
+    (impl $arr:ident { [$start:tt ..] : [ $value:expr ; $len:expr ] }) => {
+        let start = $start;
+        let end = start + $len;
+        let len = $arr.len();
+        $crate::__fill!($arr (start) (.. if end < len { end } else { len }) (1) $value);
+    };
+    (impl $arr:ident { [$start:tt ..] : [] }) => {};
+    (impl $arr:ident { [$start:tt ..] : [ $($value:expr),* $(,)? ] }) => {
+        let mut i = $start;
+        let len = $arr.len();
+        $(
+            if i < len {
+                $arr[i] = $value;
+            }
+            i += 1;
+        )*
+    };
+    (impl $arr:ident { [$start:tt ..] : $value:expr }) => {
+        let mut i = $start;
+        let start = i;
+        let arr_inner = $value;
+        let end = i + arr_inner.len();
+        while i < end && i < $arr.len() {
+            $arr[i] = ::core::clone::Clone::clone(&arr_inner[i - start]);
+            i += 1;
+        }
+    };
+    (impl $arr:ident { [$start:tt] : [ $($value:expr),* $(,)? ] * $count:tt }) => {
+        $crate::arr!(impl @repeat [] $arr ($start) ($count) [ $($value),* ]);
+    };
+    (impl $arr:ident { [$start:tt] : [ $value:expr ; $len:expr ] }) => {
+        let start = $start;
+        $crate::__fill!($arr (start) (.. start + $len) (1) $value);
+    };
+    (impl $arr:ident { [$start:tt] : [] }) => {};
+    (impl $arr:ident { [$start:tt] : [ $($value:expr),* $(,)? ] }) => {
+        let mut i = $start;
+        $(
+            $arr[i] = $value;
+            i += 1;
+        )*
+    };
+    // the length of the sub-slice is checked first, so it can be copied as an
+    // array of a fixed size
+    (impl $arr:ident { [$start:tt] : $src:tt [ $($range:tt)* ] of $len:tt }) => {
+        let src = &$src[$($range)*];
+        assert!(
+            src.len() == $len,
+            "array-lit: the source slice has length {}, but {} was expected",
+            src.len(),
+            $len,
+        );
+        let src: &[_; $len] = ::core::convert::TryInto::try_into(src).unwrap();
+        $crate::arr!(impl $arr { [$start] : src });
+    };
+    (impl $arr:ident { [$start:tt] : $value:expr }) => {
+        {
+            #[allow(unused_imports)]
+            use $crate::{__CopyByIndex, __CopyFromSlice};
+            let arr_inner = $value;
+            (&mut $crate::__Dest(&mut $arr)).copy_from($start, &arr_inner);
+        }
+    };
+    (impl $arr:ident { $first:tt $(| $key:tt)+ : $value:expr }) => {
+        let value = $value;
+        $crate::arr!(impl $arr { $first : ::core::clone::Clone::clone(&value) });
+        $( $crate::arr!(impl $arr { $key : ::core::clone::Clone::clone(&value) }); )+
+    };
+    // literal indices are already checked at compile time if the length is a
+    // literal as well, and also work in `static` items
+    (impl $arr:ident { $key:literal : $value:expr }) => {
+        $arr[$key] = $value;
+    };
+    (impl $arr:ident { $key:tt : $value:expr }) => {
+        let value = $value;
+        let index = $crate::__index!($key);
+        $crate::arr!(impl @bounds [] $arr index);
+        $arr[index] = value;
+    };
+}
+
+/// Like [`arr!`](macro.arr.html), but can be used in `const` and `static`
+/// items.
+///
+/// See [the module level documentation](index.html) for more.
+///
+/// # Example
+///
+///```rust
+/// # use array_lit::const_arr;
+/// const HEADER: [u8; 4] = [1, 2, 3, 4];
+/// const A: [u8; 8] = const_arr![0; 8; { [0]: HEADER, [6]: [9; 2] }];
+/// assert_eq!(A, [1, 2, 3, 4, 0, 0, 9, 9]);
+/// ```
+#[macro_export]
+macro_rules! const_arr {
+    [$item:expr ; $len:expr ; { $($body:tt)* }] => {
+        {
+            #[allow(unused_mut, unused_assignments, unused_parens)]
+            {
+                let mut arr = [$item ; $len];
+                $crate::arr!(impl arr @block @const { $($body)* });
+                arr
+            }
+        }
+    };
+    [$item:expr ; $len:expr ; { $($body:tt)* } : $ty:ty] => {
+        $crate::__typed::<$ty, _>($crate::const_arr![$item ; $len ; { $($body)* }])
+    };
+    [$item:expr ; $len:expr ; as $a:ident { $($body:tt)* }] => {
+        $crate::arr!(impl @as (@const $item ; $len) $a [] [] $($body)*)
+    };
+
+    [$($tt:tt)*] => {
+        $crate::arr![$($tt)*]
+    };
+}
+
+/// Like [`arr!`](macro.arr.html), but returns an error instead of panicking
+/// when an index is out of bounds.
+///
+/// The result is a `Result<[T; N], IndexError>`. If several indices are out
+/// of bounds, the error contains the first one. For an index from the end
+/// like `^i`, the error contains `i`. A `then` closure is only called if
+/// there is no error. Blocks with a named array (`as a`) aren't supported.
+///
+/// With `collect`, the result is a `Result<[T; N], TooFewItemsError>`
+/// instead, which is an error if the iterator yields fewer than `N` items.
+///
+/// # Example
+///
+///```rust
+/// # use array_lit::{try_arr, IndexError, TooFewItemsError};
+/// let a = try_arr![0; 4; { 1: 1, 3: 3 }];
+/// assert_eq!(a, Ok([0, 1, 0, 3]));
+///
+/// let index = 7;
+/// let a = try_arr![0; 4; { index: 1 }];
+/// assert_eq!(a, Err(IndexError { index: 7, len: 4 }));
+///
+/// let a = try_arr![collect 0..3; 4];
+/// assert_eq!(a, Err(TooFewItemsError { yielded: 3, expected: 4 }));
+/// ```
+#[macro_export]
+macro_rules! try_arr {
+    [collect $iter:expr ; $len:expr] => {
+        {
+            let mut iter = ::core::iter::Iterator::fuse(
+                ::core::iter::IntoIterator::into_iter($iter),
+            );
+            let items = ::core::array::from_fn::<_, { $len }, _>(|_| iter.next());
+            let yielded = items.iter().filter(|item| item.is_some()).count();
+            if yielded < $len {
+                ::core::result::Result::Err($crate::TooFewItemsError {
+                    yielded,
+                    expected: $len,
+                })
+            } else {
+                ::core::result::Result::Ok(items.map(::core::option::Option::unwrap))
+            }
+        }
+    };
+    [fn $f:expr ; $len:expr ; { $($body:tt)* }] => {
+        {
+            #[allow(unused_mut, unused_assignments, unused_parens)]
+            {
+                let mut arr = $crate::__TryArr::without_scratch($crate::arr![fn $f ; $len]);
+                $crate::arr!(impl arr @block { $($body)* });
+                arr.finish()
+            }
+        }
+    };
+    [default ; $len:expr ; { $($body:tt)* }] => {
+        {
+            #[allow(unused_mut, unused_assignments, unused_parens)]
+            {
+                let mut arr = $crate::__TryArr::new(
+                    $crate::arr![default ; $len],
+                    ::core::default::Default::default(),
+                );
+                $crate::arr!(impl arr @block { $($body)* });
+                arr.finish()
+            }
+        }
+    };
+    [each $item:expr ; $len:expr ; { $($body:tt)* }] => {
+        {
+            #[allow(unused_mut, unused_assignments, unused_parens)]
+            {
+                let mut arr = $crate::__TryArr::without_scratch($crate::arr![each $item ; $len]);
+                $crate::arr!(impl arr @block { $($body)* });
+                arr.finish()
+            }
+        }
+    };
+    [clone $item:expr ; $len:expr ; { $($body:tt)* }] => {
+        {
+            #[allow(unused_mut, unused_assignments, unused_parens)]
+            {
+                let item = $item;
+                let scratch = ::core::clone::Clone::clone(&item);
+                let mut arr = $crate::__TryArr::new($crate::arr![clone item ; $len], scratch);
+                $crate::arr!(impl arr @block { $($body)* });
+                arr.finish()
+            }
+        }
+    };
+    [_ ; $len:expr ; { .. : fn $f:expr $(, $($body:tt)*)? }] => {
+        $crate::try_arr![fn $f ; $len ; { $($($body)*)? }]
+    };
+    [_ ; $len:expr ; { .. : $item:expr $(, $($body:tt)*)? }] => {
+        $crate::try_arr![each $item ; $len ; { $($($body)*)? }]
+    };
+    [_ ; $len:expr ; { $($body:tt)* }] => {
+        $crate::__error!(omitted_fill)
+    };
+    // the length is inferred from literal indices, so they are all in bounds
+    [$item:expr ; _ ; { $($body:tt)* }] => {
+        ::core::result::Result::<_, $crate::IndexError>::Ok($crate::arr![$item ; _ ; { $($body)* }])
+    };
+    [$item:expr ; $len:expr ; { $($body:tt)* }] => {
+        {
+            #[allow(unused_mut, unused_assignments, unused_parens)]
+            {
+                let item = $item;
+                let mut arr = $crate::__TryArr::new([item ; $len], item);
+                $crate::arr!(impl arr @block { $($body)* });
+                arr.finish()
+            }
+        }
+    };
+    [fn $g:expr ; $len:expr ; { $($body:tt)* } then $f:expr] => {
+        $crate::try_arr![fn $g ; $len ; { $($body)* }].map(|mut arr| {
+            arr.iter_mut().for_each($f);
+            arr
+        })
+    };
+    [default ; $len:expr ; { $($body:tt)* } then $f:expr] => {
+        $crate::try_arr![default ; $len ; { $($body)* }].map(|mut arr| {
+            arr.iter_mut().for_each($f);
+            arr
+        })
+    };
+    [each $g:expr ; $len:expr ; { $($body:tt)* } then $f:expr] => {
+        $crate::try_arr![each $g ; $len ; { $($body)* }].map(|mut arr| {
+            arr.iter_mut().for_each($f);
+            arr
+        })
+    };
+    [clone $item:expr ; $len:expr ; { $($body:tt)* } then $f:expr] => {
+        $crate::try_arr![clone $item ; $len ; { $($body)* }].map(|mut arr| {
+            arr.iter_mut().for_each($f);
+            arr
+        })
+    };
+    [$item:expr ; $len:expr ; { $($body:tt)* } then $f:expr] => {
+        $crate::try_arr![$item ; $len ; { $($body)* }].map(|mut arr| {
+            arr.iter_mut().for_each($f);
+            arr
+        })
+    };
+    // an explicit element type after the block
+    [fn $f:expr ; $len:expr ; { $($body:tt)* } : $ty:ty] => {
+        $crate::try_arr![fn $f ; $len ; { $($body)* }].map($crate::__typed::<$ty, _>)
+    };
+    [default ; $len:expr ; { $($body:tt)* } : $ty:ty] => {
+        $crate::try_arr![default ; $len ; { $($body)* }].map($crate::__typed::<$ty, _>)
+    };
+    [each $item:expr ; $len:expr ; { $($body:tt)* } : $ty:ty] => {
+        $crate::try_arr![each $item ; $len ; { $($body)* }].map($crate::__typed::<$ty, _>)
+    };
+    [clone $item:expr ; $len:expr ; { $($body:tt)* } : $ty:ty] => {
+        $crate::try_arr![clone $item ; $len ; { $($body)* }].map($crate::__typed::<$ty, _>)
+    };
+    [_ ; $len:expr ; { $($body:tt)* } : $ty:ty] => {
+        $crate::try_arr![_ ; $len ; { $($body)* }].map($crate::__typed::<$ty, _>)
+    };
+    [$item:expr ; _ ; { $($body:tt)* } : $ty:ty] => {
+        $crate::try_arr![$item ; _ ; { $($body)* }].map($crate::__typed::<$ty, _>)
+    };
+    [$item:expr ; $len:expr ; { $($body:tt)* } : $ty:ty] => {
+        $crate::try_arr![$item ; $len ; { $($body)* }].map($crate::__typed::<$ty, _>)
+    };
+    // other forms with a block would panic instead of returning an error
+    [fn $f:expr ; $len:expr ; $($rest:tt)+] => {
+        $crate::__error!(try_unsupported try_arr)
+    };
+    [default ; $len:expr ; $($rest:tt)+] => {
+        $crate::__error!(try_unsupported try_arr)
+    };
+    [each $item:expr ; $len:expr ; $($rest:tt)+] => {
+        $crate::__error!(try_unsupported try_arr)
+    };
+    [clone $item:expr ; $len:expr ; $($rest:tt)+] => {
+        $crate::__error!(try_unsupported try_arr)
+    };
+    [$item:expr ; $len:expr ; { $($body:tt)* } $($rest:tt)+] => {
+        $crate::__error!(try_unsupported try_arr)
+    };
+    [$item:expr ; $len:expr ; as $($rest:tt)+] => {
+        $crate::__error!(try_unsupported try_arr)
+    };
+
+    [$($tt:tt)*] => {
+        ::core::result::Result::<_, $crate::IndexError>::Ok($crate::arr![$($tt)*])
+    };
+}
+
+/// Converts a slice into an array of the given length.
+///
+/// Returns a [`SliceLengthError`](struct.SliceLengthError.html) if the slice
+/// is shorter or longer than the array. The source can be anything that can
+/// be indexed with `..`, such as a slice, an array or a `Vec`. `Copy` elements
+/// are copied at once, other elements are cloned one by one.
+///
+/// See [the module level documentation](index.html) for more.
+///
+/// # Example
+///
+///```rust
+/// # use array_lit::{arr_from_slice, SliceLengthError};
+/// let src = [1, 2, 3, 4];
+/// assert_eq!(arr_from_slice![src[1..]; 3], Ok([2, 3, 4]));
+/// assert_eq!(
+///     arr_from_slice![src; 2],
+///     Err(SliceLengthError { len: 4, expected: 2 }),
+/// );
+/// ```
+#[macro_export]
+macro_rules! arr_from_slice {
+    [$src:expr ; $len:expr] => {
+        {
+            #[allow(unused_imports)]
+            use $crate::{__CloneToArray, __CopyToArray};
+            let src: &[_] = &$src[..];
+            if src.len() == $len {
+                ::core::result::Result::Ok((&$crate::__FromSlice(src)).to_array::<{ $len }>())
+            } else {
+                ::core::result::Result::Err($crate::SliceLengthError {
+                    len: src.len(),
+                    expected: $len,
+                })
+            }
+        }
+    };
+}
+
+/// Creates an array of consecutive integers.
+///
+/// `arr_range![N]` returns `[0, 1, ..., N - 1]` as a `[usize; N]`, and
+/// `arr_range![start; N]` starts at `start` instead. The elements have the
+/// type of `start`, so any integer type can be used. This also works in
+/// `const` items.
+///
+/// See [the module level documentation](index.html) for more.
+///
+/// # Example
+///
+///```rust
+/// # use array_lit::arr_range;
+/// assert_eq!(arr_range![4], [0_usize, 1, 2, 3]);
+/// assert_eq!(arr_range![b'a'; 3], *b"abc");
+/// ```
+#[macro_export]
+macro_rules! arr_range {
+    [$start:expr ; $len:expr] => {
+        {
+            let mut arr = [$start ; $len];
+            let mut i = 1;
+            while i < arr.len() {
+                arr[i] = arr[i - 1] + 1;
+                i += 1;
+            }
+            arr
+        }
+    };
+    [$len:expr] => {
+        $crate::arr_range![0_usize ; $len]
+    };
+}
+
+/// A macro for array literals without a fill value.
+///
+/// Every index from `0` to the length must be assigned exactly once, so the
+/// element type doesn't need to implement `Copy` or `Clone`. Only literal
+/// indices are supported, which allows checking this at compile time. If the
+/// length isn't a literal, e.g. because it is a const generic parameter, it
+/// is checked at runtime instead, before any value is computed.
+///
+/// See [the module level documentation](index.html) for more.
+///
+/// # Example
+///
+///```rust
+/// # use array_lit::arr_uninit;
+/// let a = arr_uninit![3; { 0: String::from("a"), 2: String::new(), 1: "b".into() }];
+/// assert_eq!(a, ["a", "b", ""]);
+/// ```
+///
+/// # How it works
+///
+/// The array is created as an uninitialized `[MaybeUninit<T>; N]`, then
+/// every entry is written with `MaybeUninit::new`. Afterwards, the array is
+/// reinterpreted as `[T; N]`.
+///
+/// This is sound because an assertion checks that the indices are exactly
+/// `0..N` in some order, so every element has been written when the array is
+/// converted. The assertion also rules out duplicate and out of bounds
+/// indices. If a value panics, the elements that were already written are
+/// leaked, but never dropped twice or read uninitialized.
+#[macro_export]
+macro_rules! arr_uninit {
+    [$len:literal ; { $( $index:literal : $value:expr ),* $(,)? }] => {
+        {
+            $crate::__error!(const not_covered $crate::__covers_all(&[$($index),*], $len));
+            $crate::arr_uninit!(impl $len ; { $( $index : $value ),* })
+        }
+    };
+    [$len:expr ; { $( $index:literal : $value:expr ),* $(,)? }] => {
+        {
+            assert!(
+                $crate::__covers_all(&[$($index),*], $len),
+                "array-lit: every index must be assigned exactly once",
+            );
+            $crate::arr_uninit!(impl $len ; { $( $index : $value ),* })
+        }
+    };
+
+    // Implementation details:
+    (impl $len:expr ; { $( $index:literal : $value:expr ),* }) => {
+        {
+            #[allow(unused_mut)]
+            {
+                let mut arr: [::core::mem::MaybeUninit<_>; $len] = $crate::__uninit_array();
+                $( arr[$index] = ::core::mem::MaybeUninit::new($value); )*
+                // SAFETY: the assertion before this macro call guarantees
+                // that every element has been initialized
+                unsafe { $crate::__assume_init(arr) }
+            }
+        }
+    };
+}
+
+/// Creates a zeroed array of a [`Zeroable`](trait.Zeroable.html) type, and
+/// then applies the entries of the block, which accepts the same syntax as
+/// [`arr!`](macro.arr.html).
+///
+/// See [the module level documentation](index.html) for more.
+///
+/// # Example
+///
+///```rust
+/// # use array_lit::zeroed_arr;
+/// let a = zeroed_arr![u8; 4; { 0: 1, 2..4: 5 }];
+/// assert_eq!(a, [1, 0, 5, 5]);
+/// ```
+///
+/// # How it works
+///
+/// The array is created with `MaybeUninit::zeroed().assume_init()`. This is
+/// sound because the element type must implement the unsafe `Zeroable` trait,
+/// which guarantees that zero bytes are a valid value. Types that don't
+/// implement it are rejected at compile time:
+///
+/// ```compile_fail
+/// # use array_lit::zeroed_arr;
+/// let a = zeroed_arr![&u8; 4];
+/// ```
+#[macro_export]
+macro_rules! zeroed_arr {
+    [$ty:ty ; $len:expr ; { $($body:tt)* }] => {
+        {
+            #[allow(unused_mut, unused_assignments, unused_parens)]
+            {
+                let mut arr = $crate::zeroed_arr![$ty ; $len];
+                $crate::arr!(impl arr @block { $($body)* });
+                arr
+            }
+        }
+    };
+    [$ty:ty ; $len:expr] => {
+        $crate::__zeroed::<$ty, { $len }>()
+    };
+}
+
+/// Like [`arr!`](macro.arr.html), but fails to compile if the same literal
+/// index appears more than once in the block.
+///
+/// Only single indices like `3: x` and `1 | 4: x` are compared; other entries,
+/// such as ranges or indices that aren't literals, are ignored.
+///
+/// See [the module level documentation](index.html) for more.
+///
+/// # Example
+///
+///```rust
+/// # use array_lit::arr_strict;
+/// let a = arr_strict![0; 4; { 0: 1, 2 | 3: 5 }];
+/// assert_eq!(a, [1, 0, 5, 5]);
+/// ```
+///
+/// ```compile_fail
+/// # use array_lit::arr_strict;
+/// let a = arr_strict![0; 4; { 0: 1, 2: 5, 0: 2 }];
+/// ```
+#[macro_export]
+macro_rules! arr_strict {
+    // Implementation details:
+    // collects the literal indices, and skips other entries token by token
+    (impl @unique [$($indices:tt)*] $(,)?) => {
+        $crate::__error!(const duplicate !$crate::__has_duplicate(&[$($indices)*]));
+    };
+    (impl @unique [$($indices:tt)*] , $($rest:tt)*) => {
+        $crate::arr_strict!(impl @unique [$($indices)*] $($rest)*);
+    };
+    (impl @unique [$($indices:tt)*]
+        $i1:literal : $v1:expr , $i2:literal : $v2:expr , $i3:literal : $v3:expr , $i4:literal : $v4:expr , $i5:literal : $v5:expr , $i6:literal : $v6:expr , $i7:literal : $v7:expr , $i8:literal : $v8:expr , $i9:literal : $v9:expr , $i10:literal : $v10:expr , $i11:literal : $v11:expr , $i12:literal : $v12:expr , $i13:literal : $v13:expr , $i14:literal : $v14:expr , $i15:literal : $v15:expr , $i16:literal : $v16:expr
+        $(, $($rest:tt)*)?
+    ) => {
+        $crate::arr_strict!(impl @unique
+            [$($indices)* $i1, $i2, $i3, $i4, $i5, $i6, $i7, $i8, $i9, $i10, $i11, $i12, $i13, $i14, $i15, $i16,]
+            $($($rest)*)?
+        );
+    };
+    (impl @unique [$($indices:tt)*] $($index:literal)|+ : $value:expr $(, $($rest:tt)*)?) => {
+        $crate::arr_strict!(impl @unique [$($indices)* $($index,)+] $($($rest)*)?);
+    };
+    (impl @unique [$($indices:tt)*] @skip , $($rest:tt)*) => {
+        $crate::arr_strict!(impl @unique [$($indices)*] $($rest)*);
+    };
+    (impl @unique [$($indices:tt)*] @skip $skip:tt $($rest:tt)*) => {
+        $crate::arr_strict!(impl @unique [$($indices)*] @skip $($rest)*);
+    };
+    (impl @unique [$($indices:tt)*] @skip) => {
+        $crate::arr_strict!(impl @unique [$($indices)*]);
+    };
+    (impl @unique [$($indices:tt)*] $skip:tt $($rest:tt)*) => {
+        $crate::arr_strict!(impl @unique [$($indices)*] @skip $($rest)*);
+    };
+
+    [fn $f:expr ; $len:expr ; { $($body:tt)* } $($tail:tt)*] => {
+        {
+            $crate::arr_strict!(impl @unique [] $($body)*);
+            $crate::arr![fn $f ; $len ; { $($body)* } $($tail)*]
+        }
+    };
+    [default ; $len:expr ; { $($body:tt)* } $($tail:tt)*] => {
+        {
+            $crate::arr_strict!(impl @unique [] $($body)*);
+            $crate::arr![default ; $len ; { $($body)* } $($tail)*]
+        }
+    };
+    [each $item:expr ; $len:expr ; { $($body:tt)* } $($tail:tt)*] => {
+        {
+            $crate::arr_strict!(impl @unique [] $($body)*);
+            $crate::arr![each $item ; $len ; { $($body)* } $($tail)*]
+        }
+    };
+    [clone $item:expr ; $len:expr ; { $($body:tt)* } $($tail:tt)*] => {
+        {
+            $crate::arr_strict!(impl @unique [] $($body)*);
+            $crate::arr![clone $item ; $len ; { $($body)* } $($tail)*]
+        }
+    };
+    [$item:expr ; _ ; { $($body:tt)* } $($tail:tt)*] => {
+        {
+            $crate::arr_strict!(impl @unique [] $($body)*);
+            $crate::arr![$item ; _ ; { $($body)* } $($tail)*]
+        }
+    };
+    [$item:expr ; $len:expr ; { $($body:tt)* } $($tail:tt)*] => {
+        {
+            $crate::arr_strict!(impl @unique [] $($body)*);
+            $crate::arr![$item ; $len ; { $($body)* } $($tail)*]
+        }
+    };
+    [$($tt:tt)*] => {
+        $crate::arr![$($tt)*]
+    };
+}
+
+/// Concatenates several arrays into one.
+///
+/// The elements must implement `Copy`. The length of the result is computed
+/// at compile time, so every argument must either be an array literal or a
+/// constant. The macro can be used in `const` and `static` items.
+///
+/// See [the module level documentation](index.html) for more.
+///
+/// # Example
+///
+///```rust
+/// # use array_lit::arr_concat;
+/// const LOW: [u8; 2] = [0, 1];
+/// const TABLE: [u8; 6] = arr_concat![LOW, [2, 3], [9; 2]];
+/// assert_eq!(TABLE, [0, 1, 2, 3, 9, 9]);
+/// ```
+#[macro_export]
+macro_rules! arr_concat {
+    // Implementation details:
+    (impl @munch ($($len:tt)*) ($($parts:tt)*) [$($arr:tt)*] $(, $($rest:tt)*)?) => {
+        $crate::arr_concat!(
+            impl @munch
+            ($($len)* + $crate::arr_concat!(impl @len [$($arr)*]))
+            ($($parts)* [$($arr)*],)
+            $($($rest)*)?
+        )
+    };
+    (impl @munch ($($len:tt)*) ($($parts:tt)*) $arg:literal $(, $($rest:tt)*)?) => {
+        $crate::__error!(concat_not_array $arg)
+    };
+    (impl @munch ($($len:tt)*) ($($parts:tt)*) $arr:expr $(, $($rest:tt)*)?) => {
+        $crate::arr_concat!(
+            impl @munch
+            ($($len)* + $arr.len())
+            ($($parts)* $arr,)
+            $($($rest)*)?
+        )
+    };
+    (impl @munch ($($len:tt)*) ($($parts:expr,)*)) => {
+        $crate::__concat::<_, { $($len)* }>(&[$(&$parts),*])
+    };
+
+    (impl @len [$($item:expr),* $(,)?]) => {
+        0 $(+ $crate::arr_concat!(impl @one $item))*
+    };
+    (impl @len [$item:expr ; $len:expr]) => {
+        $len
+    };
+    (impl @one $item:expr) => {
+        1
+    };
+
+    // comma-separated list of arrays:
+    [$($tt:tt)*] => {
+        $crate::arr_concat!(impl @munch (0) () $($tt)*)
+    };
+}
+
+/// A macro for two-dimensional array literals with superpowers.
+///
+/// The dimensions are given as `rows, columns`. Single cells are set with a
+/// `(row, column)` index, whole rows with a `(row,)` index, and whole columns
+/// with `col column`. A column must have one value per row. If the number of
+/// rows is a literal, a mismatch is a compile error, otherwise it panics.
+///
+/// See [the module level documentation](index.html) for more.
+///
+/// # Example
+///
+///```rust
+/// # use array_lit::arr2d;
+/// let a = arr2d![0; 2, 3; { (0, 1): 1, (1,): [2, 3, 4], col 0: [5, 6] }];
+/// assert_eq!(a, [[5, 1, 0], [6, 3, 4]]);
+/// ```
+#[macro_export]
+macro_rules! arr2d {
+    [$item:expr ; $rows:literal , $cols:expr ; { $($body:tt)* }] => {
+        $crate::arr2d!(impl @new $item ; ($rows) , $cols ; { $($body)* })
+    };
+    [$item:expr ; $rows:expr , $cols:expr ; { $($body:tt)* }] => {
+        $crate::arr2d!(impl @new $item ; ($rows) , $cols ; { $($body)* })
+    };
+    [$item:expr ; $rows:expr , $cols:expr] => {
+        [[$item ; $cols] ; $rows]
+    };
+
+    // Implementation details:
+    (impl @new $item:expr ; ($rows:tt) , $cols:expr ; { $($body:tt)* }) => {
+        {
+            #[allow(unused_mut, unused_assignments)]
+            {
+                let mut arr = [[$item ; $cols] ; $rows];
+                $crate::arr2d!(impl arr @munch ($rows) $($body)*);
+                arr
+            }
+        }
+    };
+    (impl $arr:ident @munch $rows:tt $(,)?) => {};
+    (impl $arr:ident @munch $rows:tt , $($rest:tt)*) => {
+        $crate::arr2d!(impl $arr @munch $rows $($rest)*);
+    };
+    (impl $arr:ident @munch $rows:tt
+        col $col:tt : [ $($value:expr),* $(,)? ] $(, $($rest:tt)*)?
+    ) => {
+        $crate::arr2d!(impl @check $rows [ $($value),* ]);
+        {
+            let col = $col;
+            let mut row = 0;
+            $(
+                $arr[row][col] = $value;
+                row += 1;
+            )*
+        }
+        $crate::arr2d!(impl $arr @munch $rows $($($rest)*)?);
+    };
+    (impl $arr:ident @munch $rows:tt ($($index:tt)*) : $value:expr $(, $($rest:tt)*)?) => {
+        $crate::arr2d!(impl $arr { ($($index)*) : $value });
+        $crate::arr2d!(impl $arr @munch $rows $($($rest)*)?);
+    };
+    (impl @check ($rows:literal) [ $($value:expr),* ]) => {
+        $crate::__error!(const column_len 0 $(+ $crate::arr!(impl @one $value))* == $rows);
+    };
+    (impl @check ($rows:expr) [ $($value:expr),* ]) => {
+        let len = 0 $(+ $crate::arr!(impl @one $value))*;
+        assert!(
+            len == $rows,
+            "array-lit: the column has {} values, but there are {} rows",
+            len,
+            $rows,
+        );
+    };
+    (impl $arr:ident { ($row:expr , $col:expr) : $value:expr }) => {
+        $arr[$row][$col] = $value;
+    };
+    (impl $arr:ident { ($row:expr ,) : $value:expr }) => {
+        $arr[$row] = $value;
+    };
+}
+
+/// A macro for three-dimensional array literals with superpowers.
+///
+/// The dimensions are given as `x, y, z`, and the result is a
+/// `[[[T; z]; y]; x]`. Single cells are set with an `(x, y, z)` index, which
+/// assigns `arr[x][y][z]`. An index with fewer or more coordinates is a compile
+/// error.
+///
+/// See [the module level documentation](index.html) for more.
+///
+/// # Example
+///
+///```rust
+/// # use array_lit::arr3d;
+/// let a = arr3d![0; 2, 2, 2; { (1, 0, 1): 9 }];
+/// assert_eq!(a, [[[0, 0], [0, 0]], [[0, 9], [0, 0]]]);
+/// ```
+#[macro_export]
+macro_rules! arr3d {
+    [$item:expr ; $x:expr , $y:expr , $z:expr ; { $($body:tt)* }] => {
+        {
+            #[allow(unused_mut, unused_assignments)]
+            {
+                let mut arr = [[[$item ; $z] ; $y] ; $x];
+                $crate::arr3d!(impl arr @munch $($body)*);
+                arr
+            }
+        }
+    };
+    [$item:expr ; $x:expr , $y:expr , $z:expr] => {
+        [[[$item ; $z] ; $y] ; $x]
+    };
+
+    // Implementation details:
+    (impl $arr:ident @munch $(,)?) => {};
+    (impl $arr:ident @munch , $($rest:tt)*) => {
+        $crate::arr3d!(impl $arr @munch $($rest)*);
+    };
+    (impl $arr:ident @munch ($($index:tt)*) : $value:expr $(, $($rest:tt)*)?) => {
+        $crate::arr3d!(impl $arr { ($($index)*) : $value });
+        $crate::arr3d!(impl $arr @munch $($($rest)*)?);
+    };
+    (impl $arr:ident { ($x:expr , $y:expr , $z:expr $(,)?) : $value:expr }) => {
+        $arr[$x][$y][$z] = $value;
+    };
+    (impl $arr:ident { ($($index:tt)*) : $value:expr }) => {
+        $crate::__error!(index_3d [$($index)*]);
+    };
+}
+
+/// Creates a two-dimensional array from a flat list of values, which are
+/// placed row by row.
+///
+/// The dimensions are given as `rows, columns` like in
+/// [`arr2d!`](macro.arr2d.html). The number of values must be
+/// `rows * columns`. If the dimensions are literals, a mismatch is a compile
+/// error, otherwise it panics.
+///
+/// # Example
+///
+///```rust
+/// # use array_lit::arr2d_flat;
+/// let a = arr2d_flat![0; 2, 3; [1, 2, 3, 4, 5, 6]];
+/// assert_eq!(a, [[1, 2, 3], [4, 5, 6]]);
+/// ```
+#[macro_export]
+macro_rules! arr2d_flat {
+    [$item:expr ; $rows:literal , $cols:literal ; [ $($value:expr),* $(,)? ]] => {
+        {
+            $crate::__error!(const flat_len 0 $(+ $crate::arr!(impl @one $value))* == $rows * $cols);
+            $crate::arr2d_flat!(impl $item ; $rows , $cols ; [ $($value),* ])
+        }
+    };
+    [$item:expr ; $rows:expr , $cols:expr ; [ $($value:expr),* $(,)? ]] => {
+        {
+            let len = 0 $(+ $crate::arr!(impl @one $value))*;
+            assert!(
+                len == $rows * $cols,
+                "array-lit: {} values were given, but {} * {} were expected",
+                len,
+                $rows,
+                $cols,
+            );
+            $crate::arr2d_flat!(impl $item ; $rows , $cols ; [ $($value),* ])
+        }
+    };
+
+    // Implementation details:
+    (impl $item:expr ; $rows:expr , $cols:expr ; [ $($value:expr),* ]) => {
+        {
+            #[allow(unused_mut, unused_variables, unused_assignments)]
+            {
+                let mut arr = [[$item ; $cols] ; $rows];
+                let cols = $cols;
+                let mut i = 0;
+                $(
+                    arr[i / cols][i % cols] = $value;
+                    i += 1;
+                )*
+                arr
+            }
+        }
+    };
+}
+
+/// Creates a two-dimensional array from rows that are stored as columns.
+///
+/// The dimensions are given as `rows, columns` of the input, which is a list
+/// of rows. The result has the type `[[T; rows]; columns]`, and the value in
+/// row `r` and column `c` of the input ends up in `out[c][r]`. If the
+/// dimensions are literals, a wrong number of rows or values in a row is a
+/// compile error, otherwise it panics.
+///
+/// # Example
+///
+///```rust
+/// # use array_lit::arr_transpose;
+/// let a = arr_transpose![0; 2, 3; rows [[1, 2, 3], [4, 5, 6]]];
+/// assert_eq!(a, [[1, 4], [2, 5], [3, 6]]);
+/// ```
+#[macro_export]
+macro_rules! arr_transpose {
+    [$item:expr ; $rows:literal , $cols:literal ; rows [ $([ $($value:expr),* $(,)? ]),* $(,)? ]] => {
+        {
+            $crate::__error!(const transpose_rows 0 $(+ $crate::arr!(impl @one [$($value),*]))* == $rows);
+            $(
+                $crate::__error!(const transpose_row_len 0 $(+ $crate::arr!(impl @one $value))* == $cols);
+            )*
+            $crate::arr_transpose!(impl $item ; $rows , $cols ; [ $([ $($value),* ]),* ])
+        }
+    };
+    [$item:expr ; $rows:expr , $cols:expr ; rows [ $([ $($value:expr),* $(,)? ]),* $(,)? ]] => {
+        {
+            let rows = 0 $(+ $crate::arr!(impl @one [$($value),*]))*;
+            assert!(
+                rows == $rows,
+                "array-lit: {} rows were given, but {} were expected",
+                rows,
+                $rows,
+            );
+            $(
+                let len = 0 $(+ $crate::arr!(impl @one $value))*;
+                assert!(
+                    len == $cols,
+                    "array-lit: a row has {} values, but {} were expected",
+                    len,
+                    $cols,
+                );
+            )*
+            $crate::arr_transpose!(impl $item ; $rows , $cols ; [ $([ $($value),* ]),* ])
+        }
+    };
+
+    // Implementation details:
+    (impl $item:expr ; $rows:expr , $cols:expr ; [ $([ $($value:expr),* ]),* ]) => {
+        {
+            #[allow(unused_mut, unused_variables, unused_assignments)]
+            {
+                let mut arr = [[$item ; $rows] ; $cols];
+                let mut r = 0;
+                $(
+                    let mut c = 0;
+                    $(
+                        arr[c][r] = $value;
+                        c += 1;
+                    )*
+                    r += 1;
+                )*
+                arr
+            }
+        }
+    };
+}
+
+/// A macro for `Vec` literals with superpowers.
+///
+/// See [the module level documentation](index.html) for more.
+///
+/// > This macro requires the **`std`** feature (enabled by default) or the
+/// > **`alloc`** feature
+///
+/// # Example
+///
+///```rust
+/// # use array_lit::vec;
+/// let a = vec![1; 5; { [0]: [1, 2], 4: 0 }];
+/// assert_eq!(a, std::vec![1, 2, 1, 1, 0]);
+/// ```
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[macro_export]
+macro_rules! vec {
+    [from $base:expr ; { $($body:tt)* }] => {
+        {
+            #[allow(unused_mut, unused_assignments, unused_parens)]
+            {
+                let mut vec = <[_]>::to_vec(&$base[..]);
+                $crate::arr!(impl vec @block { $($body)* });
+                vec
+            }
+        }
+    };
+    [fn $g:expr ; $len:expr ; { $($body:tt)* } then $f:expr] => {
+        {
+            let mut vec = $crate::vec![fn $g ; $len ; { $($body)* }];
+            vec.iter_mut().for_each($f);
+            vec
+        }
+    };
+    [default ; $len:expr ; { $($body:tt)* } then $f:expr] => {
+        {
+            let mut vec = $crate::vec![default ; $len ; { $($body)* }];
+            vec.iter_mut().for_each($f);
+            vec
+        }
+    };
+    [each $g:expr ; $len:expr ; { $($body:tt)* } then $f:expr] => {
+        {
+            let mut vec = $crate::vec![each $g ; $len ; { $($body)* }];
+            vec.iter_mut().for_each($f);
+            vec
+        }
+    };
+    [fn $f:expr ; $len:expr ; { $($body:tt)* }] => {
+        {
+            #[allow(unused_mut, unused_assignments, unused_parens)]
+            {
+                let mut vec = $crate::vec![fn $f ; $len];
+                $crate::arr!(impl vec @block { $($body)* });
+                vec
+            }
+        }
+    };
+    [fn $f:expr ; $len:expr] => {
+        (0..$len).map($f).collect::<$crate::alloc::vec::Vec<_>>()
+    };
+    [default ; $len:expr ; { $($body:tt)* }] => {
+        {
+            #[allow(unused_mut, unused_assignments, unused_parens)]
+            {
+                let mut vec = $crate::vec![default ; $len];
+                $crate::arr!(impl vec @block { $($body)* });
+                vec
+            }
+        }
+    };
+    [default ; $len:expr] => {
+        (0..$len)
+            .map(|_| ::core::default::Default::default())
+            .collect::<$crate::alloc::vec::Vec<_>>()
+    };
+    [each $item:expr ; $len:expr ; { $($body:tt)* }] => {
+        {
+            #[allow(unused_mut, unused_assignments, unused_parens)]
+            {
+                let mut vec = $crate::vec![each $item ; $len];
+                $crate::arr!(impl vec @block { $($body)* });
+                vec
+            }
+        }
+    };
+    [each $item:expr ; $len:expr] => {
+        (0..$len).map(|_| $item).collect::<$crate::alloc::vec::Vec<_>>()
+    };
+    [$item:expr ; $len:tt cap $cap:tt ; { $($body:tt)* }] => {
+        {
+            #[allow(unused_mut, unused_assignments, unused_parens)]
+            {
+                let mut vec = $crate::vec![$item ; $len cap $cap];
+                $crate::arr!(impl vec @block { $($body)* });
+                vec
+            }
+        }
+    };
+    [$item:expr ; $len:tt cap $cap:tt] => {
+        {
+            let mut vec = $crate::alloc::vec::Vec::with_capacity($cap);
+            vec.resize($len, $item);
+            vec
+        }
+    };
+    [$item:expr ; auto ; { $($body:tt)* }] => {
+        {
+            #[allow(unused_mut, unused_assignments, unused_parens, unused_variables)]
+            {
+                let item = $item;
+                let mut vec = $crate::alloc::vec::Vec::new();
+                $crate::__check_overlap!($($body)*);
+                $crate::arr!(impl vec @munch [@auto item] [] $($body)*);
+                vec
+            }
+        }
+    };
+    [$item:expr ; $len:expr ; { $($body:tt)* } then $f:expr] => {
+        {
+            let mut vec = $crate::vec![$item ; $len ; { $($body)* }];
+            vec.iter_mut().for_each($f);
+            vec
+        }
+    };
+    [$item:expr ; $len:literal ; { $($body:tt)* }] => {
+        {
+            #[allow(unused_mut, unused_assignments, unused_parens)]
+            {
+                $crate::arr!(impl @check $len { $($body)* });
+                let mut vec = $crate::alloc::vec![$item ; $len];
+                $crate::arr!(impl vec @block { $($body)* });
+                vec
+            }
+        }
+    };
+    [$item:expr ; $len:expr ; { $($body:tt)* }] => {
+        {
+            #[allow(unused_mut, unused_assignments, unused_parens)]
+            {
+                let mut vec = $crate::alloc::vec![$item ; $len];
+                $crate::arr!(impl vec @block { $($body)* });
+                vec
+            }
+        }
+    };
 
-#[cfg(test)]
-mod tests;
+    // same syntax as regular array literals:
+    [$item:expr ; $len:expr] => {
+        $crate::alloc::vec![$item ; $len]
+    };
 
-/// A macro for array literals with superpowers.
+    // Implementation details:
+    (impl @list $acc:tt [$($seg:expr),*] .. $spread:expr $(, $($rest:tt)*)?) => {
+        $crate::vec!(impl @list ($acc [$($seg),*] (.. $spread)) [] $($($rest)*)?)
+    };
+    // consume up to eight elements at once, but stop before the next spread:
+    (impl @list $acc:tt [$($seg:expr),*] $a:expr , .. $spread:expr $(, $($rest:tt)*)?) => {
+        $crate::vec!(impl @list $acc [$($seg,)* $a] .. $spread $(, $($rest)*)?)
+    };
+    (impl @list $acc:tt [$($seg:expr),*] $a:expr , $b:expr , .. $spread:expr $(, $($rest:tt)*)?) => {
+        $crate::vec!(impl @list $acc [$($seg,)* $a, $b] .. $spread $(, $($rest)*)?)
+    };
+    (impl @list $acc:tt [$($seg:expr),*] $a:expr , $b:expr , $c:expr , .. $spread:expr $(, $($rest:tt)*)?) => {
+        $crate::vec!(impl @list $acc [$($seg,)* $a, $b, $c] .. $spread $(, $($rest)*)?)
+    };
+    (impl @list $acc:tt [$($seg:expr),*] $a:expr , $b:expr , $c:expr , $d:expr , .. $spread:expr $(, $($rest:tt)*)?) => {
+        $crate::vec!(impl @list $acc [$($seg,)* $a, $b, $c, $d] .. $spread $(, $($rest)*)?)
+    };
+    (impl @list $acc:tt [$($seg:expr),*] $a:expr , $b:expr , $c:expr , $d:expr , $e:expr , .. $spread:expr $(, $($rest:tt)*)?) => {
+        $crate::vec!(impl @list $acc [$($seg,)* $a, $b, $c, $d, $e] .. $spread $(, $($rest)*)?)
+    };
+    (impl @list $acc:tt [$($seg:expr),*] $a:expr , $b:expr , $c:expr , $d:expr , $e:expr , $f:expr , .. $spread:expr $(, $($rest:tt)*)?) => {
+        $crate::vec!(impl @list $acc [$($seg,)* $a, $b, $c, $d, $e, $f] .. $spread $(, $($rest)*)?)
+    };
+    (impl @list $acc:tt [$($seg:expr),*] $a:expr , $b:expr , $c:expr , $d:expr , $e:expr , $f:expr , $g:expr , .. $spread:expr $(, $($rest:tt)*)?) => {
+        $crate::vec!(impl @list $acc [$($seg,)* $a, $b, $c, $d, $e, $f, $g] .. $spread $(, $($rest)*)?)
+    };
+    (impl @list $acc:tt [$($seg:expr),*] $a:expr , $b:expr , $c:expr , $d:expr , $e:expr , $f:expr , $g:expr , $h:expr $(, $($rest:tt)*)?) => {
+        $crate::vec!(impl @list $acc [$($seg,)* $a, $b, $c, $d, $e, $f, $g, $h] $($($rest)*)?)
+    };
+    (impl @list $acc:tt [$($seg:expr),*] $item:expr $(, $($rest:tt)*)?) => {
+        $crate::vec!(impl @list $acc [$($seg,)* $item] $($($rest)*)?)
+    };
+    (impl @list [] [$($item:expr),*]) => {
+        $crate::alloc::vec![ $($item),* ]
+    };
+    (impl @list $acc:tt [$($item:expr),*]) => {
+        {
+            #[allow(clippy::vec_init_then_push)]
+            {
+                let mut vec = $crate::alloc::vec::Vec::new();
+                $crate::vec!(impl @spread vec $acc);
+                $( vec.push($item); )*
+                vec
+            }
+        }
+    };
+    (impl @spread $vec:ident []) => {};
+    (impl @spread $vec:ident ($acc:tt [$($item:expr),*] (.. $spread:expr))) => {
+        $crate::vec!(impl @spread $vec $acc);
+        $( $vec.push($item); )*
+        $vec.extend_from_slice(&$spread);
+    };
+
+    // comma-separated list, optionally with `..spread` elements:
+    [$($tt:tt)*] => {
+        $crate::vec!(impl @list [] [] $($tt)*)
+    };
+}
+
+/// Like [`vec!`](macro.vec.html), but returns an error instead of panicking
+/// when an index is out of bounds.
+///
+/// The result is a `Result<Vec<T>, IndexError>`. If several indices are out
+/// of bounds, the error contains the first one.
+///
+/// With `|i| value; len`, the value is computed for every index, and the `?`
+/// operator can be used in it. The first error is returned, and the value
+/// isn't computed for the indices after it. The error type usually needs a
+/// type annotation, because `?` converts errors with `From`.
+///
+/// > This macro requires the **`std`** feature (enabled by default) or the
+/// > **`alloc`** feature
+///
+/// # Example
+///
+///```rust
+/// # use array_lit::{try_vec, IndexError};
+/// let a = try_vec![0; 4; { 1: 1, 3: 3 }];
+/// assert_eq!(a, Ok(vec![0, 1, 0, 3]));
+///
+/// let len = 2;
+/// let a = try_vec![0; len; { 0..3: 1 }];
+/// assert_eq!(a, Err(IndexError { index: 2, len: 2 }));
+///
+/// let input = ["1", "2", "x"];
+/// let b: Result<Vec<i32>, std::num::ParseIntError> = try_vec![|i| input[i].parse()?; 2];
+/// assert_eq!(b, Ok(vec![1, 2]));
+/// ```
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[macro_export]
+macro_rules! try_vec {
+    [| $i:pat | $value:expr ; $len:expr] => {
+        $crate::__try_collect($len, |$i| ::core::result::Result::Ok($value))
+    };
+    [fn $f:expr ; $len:expr ; { $($body:tt)* }] => {
+        {
+            #[allow(unused_mut, unused_assignments, unused_parens)]
+            {
+                let mut vec = $crate::__TryArr::without_scratch($crate::vec![fn $f ; $len]);
+                $crate::arr!(impl vec @block { $($body)* });
+                vec.finish()
+            }
+        }
+    };
+    [default ; $len:expr ; { $($body:tt)* }] => {
+        {
+            #[allow(unused_mut, unused_assignments, unused_parens)]
+            {
+                let mut vec = $crate::__TryArr::new(
+                    $crate::vec![default ; $len],
+                    ::core::default::Default::default(),
+                );
+                $crate::arr!(impl vec @block { $($body)* });
+                vec.finish()
+            }
+        }
+    };
+    [each $item:expr ; $len:expr ; { $($body:tt)* }] => {
+        {
+            #[allow(unused_mut, unused_assignments, unused_parens)]
+            {
+                let mut vec = $crate::__TryArr::without_scratch($crate::vec![each $item ; $len]);
+                $crate::arr!(impl vec @block { $($body)* });
+                vec.finish()
+            }
+        }
+    };
+    // the `Vec` grows to fit every index
+    [$item:expr ; auto ; { $($body:tt)* }] => {
+        ::core::result::Result::<_, $crate::IndexError>::Ok($crate::vec![$item ; auto ; { $($body)* }])
+    };
+    [$item:expr ; $len:expr ; { $($body:tt)* }] => {
+        {
+            #[allow(unused_mut, unused_assignments, unused_parens)]
+            {
+                let item = $item;
+                let mut vec = $crate::__TryArr::new(
+                    $crate::alloc::vec![::core::clone::Clone::clone(&item) ; $len],
+                    item,
+                );
+                $crate::arr!(impl vec @block { $($body)* });
+                vec.finish()
+            }
+        }
+    };
+    [fn $g:expr ; $len:expr ; { $($body:tt)* } then $f:expr] => {
+        $crate::try_vec![fn $g ; $len ; { $($body)* }].map(|mut vec| {
+            vec.iter_mut().for_each($f);
+            vec
+        })
+    };
+    [default ; $len:expr ; { $($body:tt)* } then $f:expr] => {
+        $crate::try_vec![default ; $len ; { $($body)* }].map(|mut vec| {
+            vec.iter_mut().for_each($f);
+            vec
+        })
+    };
+    [each $g:expr ; $len:expr ; { $($body:tt)* } then $f:expr] => {
+        $crate::try_vec![each $g ; $len ; { $($body)* }].map(|mut vec| {
+            vec.iter_mut().for_each($f);
+            vec
+        })
+    };
+    [$item:expr ; $len:expr ; { $($body:tt)* } then $f:expr] => {
+        $crate::try_vec![$item ; $len ; { $($body)* }].map(|mut vec| {
+            vec.iter_mut().for_each($f);
+            vec
+        })
+    };
+    // other forms with a block would panic instead of returning an error
+    [from $base:expr ; $($rest:tt)+] => {
+        $crate::__error!(try_unsupported try_vec)
+    };
+    [fn $f:expr ; $len:expr ; $($rest:tt)+] => {
+        $crate::__error!(try_unsupported try_vec)
+    };
+    [default ; $len:expr ; $($rest:tt)+] => {
+        $crate::__error!(try_unsupported try_vec)
+    };
+    [each $item:expr ; $len:expr ; $($rest:tt)+] => {
+        $crate::__error!(try_unsupported try_vec)
+    };
+    [$item:expr ; $len:tt cap $cap:tt ; $($rest:tt)+] => {
+        $crate::__error!(try_unsupported try_vec)
+    };
+    [$item:expr ; $len:expr ; { $($body:tt)* } $($rest:tt)+] => {
+        $crate::__error!(try_unsupported try_vec)
+    };
+
+    [$($tt:tt)*] => {
+        ::core::result::Result::<_, $crate::IndexError>::Ok($crate::vec![$($tt)*])
+    };
+}
+
+/// A macro for boxed slice literals with superpowers.
+///
+/// This accepts the same syntax as [`vec!`](macro.vec.html), but returns a
+/// `Box<[T]>`. This is useful for large arrays that would overflow the stack.
+///
+/// > This macro requires the **`std`** feature (enabled by default) or the
+/// > **`alloc`** feature
+///
+/// # Example
+///
+///```rust
+/// # use array_lit::boxed_arr;
+/// let a = boxed_arr![1; 5; { [0]: [1, 2], 4: 0 }];
+/// assert_eq!(*a, [1, 2, 1, 1, 0]);
+/// ```
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[macro_export]
+macro_rules! boxed_arr {
+    [$($tt:tt)*] => {
+        $crate::vec![$($tt)*].into_boxed_slice()
+    };
+}
+
+/// A macro for `Arc<[T]>` literals with superpowers.
+///
+/// This accepts the same syntax as [`vec!`](macro.vec.html), but returns an
+/// `Arc<[T]>`, which is useful for immutable tables that are shared between
+/// threads.
+///
+/// > This macro requires the **`std`** feature (enabled by default) or the
+/// > **`alloc`** feature
+///
+/// # Example
+///
+///```rust
+/// # use array_lit::arc_arr;
+/// let a = arc_arr![0; 5; { [0]: [1, 2], 4: 3 }];
+/// assert_eq!(*a, [1, 2, 0, 0, 3]);
+/// ```
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[macro_export]
+macro_rules! arc_arr {
+    [$($tt:tt)*] => {
+        $crate::alloc::sync::Arc::<[_]>::from($crate::vec![$($tt)*])
+    };
+}
+
+/// A macro for `Rc<[T]>` literals with superpowers.
+///
+/// This accepts the same syntax as [`vec!`](macro.vec.html), but returns an
+/// `Rc<[T]>`.
+///
+/// > This macro requires the **`std`** feature (enabled by default) or the
+/// > **`alloc`** feature
+///
+/// # Example
+///
+///```rust
+/// # use array_lit::rc_arr;
+/// let a = rc_arr![0; 5; { [0]: [1, 2], 4: 3 }];
+/// assert_eq!(*a, [1, 2, 0, 0, 3]);
+/// ```
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[macro_export]
+macro_rules! rc_arr {
+    [$($tt:tt)*] => {
+        $crate::alloc::rc::Rc::<[_]>::from($crate::vec![$($tt)*])
+    };
+}
+
+/// Like [`arr!`](macro.arr.html), but also returns the indices that were
+/// assigned by the block.
+///
+/// The result is a tuple of the array and a `Vec<usize>`, which contains the
+/// indices in the order in which they were assigned. Ranges are expanded to
+/// every index they contain, and an index that is assigned several times is
+/// included several times. `base` entries are not supported.
 ///
 /// See [the module level documentation](index.html) for more.
 ///
+/// > This macro requires the **`std`** feature (enabled by default) or the
+/// > **`alloc`** feature
+///
 /// # Example
 ///
 ///```rust
-/// # use array_lit::arr;
-/// let a = arr![1; 5; { [0]: [1, 2], 4: 0 }];
-/// assert_eq!(a, [1, 2, 1, 1, 0]);
+/// # use array_lit::arr_traced;
+/// let (a, indices) = arr_traced![0; 6; { 4: 1, 1..3: 2 }];
+/// assert_eq!(a, [0, 2, 2, 0, 1, 0]);
+/// assert_eq!(indices, [4, 1, 2]);
 /// ```
+#[cfg(any(feature = "std", feature = "alloc"))]
 #[macro_export]
-macro_rules! arr {
-    [$item:expr ; $len:expr ; { $( $index:tt : $value:expr ),* $(,)? }] => {
+macro_rules! arr_traced {
+    [$item:expr ; $len:expr ; { $($body:tt)* }] => {
         {
-            #[allow(unused_mut, unused_assignments)]
+            #[allow(unused_mut, unused_assignments, unused_parens)]
             {
-                let mut arr = [$item ; $len];
-                $( $crate::arr!(impl arr { $index : $value }); )*
+                let mut arr = $crate::__Traced::new([$item ; $len]);
+                $crate::arr!(impl arr @block { $($body)* });
+                arr.finish()
+            }
+        }
+    };
+}
+
+/// Like [`arr!`](macro.arr.html), but also prints the array to stderr, like
+/// [`std::dbg!`].
+///
+/// The output contains the file, line and column of the invocation, the
+/// tokens of the invocation and the array, formatted with `{:#?}`. The array
+/// is then returned unchanged, so the elements must implement `Debug`.
+///
+/// > This macro requires the **`std`** feature (enabled by default)
+///
+/// # Example
+///
+///```rust
+/// # use array_lit::arr_dbg;
+/// let a = arr_dbg![0; 6; { 4: 1, 1..3: 2 }];
+/// assert_eq!(a, [0, 2, 2, 0, 1, 0]);
+/// ```
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! arr_dbg {
+    [$($tt:tt)*] => {
+        match $crate::arr![$($tt)*] {
+            arr => {
+                $crate::__eprintln!(
+                    "[{}:{}:{}] {} = {:#?}",
+                    ::core::file!(),
+                    ::core::line!(),
+                    ::core::column!(),
+                    ::core::concat!("arr![", ::core::stringify!($($tt)*), "]"),
+                    &arr,
+                );
                 arr
             }
         }
     };
+}
 
-    // same syntax as regular array literals:
-    [$item:expr ; $len:expr] => {
-        [$item ; $len]
+/// A macro for `String` literals with superpowers.
+///
+/// This accepts the same syntax as [`vec!`](macro.vec.html), but the elements
+/// are `char`s, which are joined into a `String`. Indices count characters,
+/// not bytes, so characters that take up several bytes can be used anywhere.
+///
+/// > This macro requires the **`std`** feature (enabled by default) or the
+/// > **`alloc`** feature
+///
+/// # Example
+///
+///```rust
+/// # use array_lit::string;
+/// let a = string!['-'; 8; { 0: '[', ^0: ']' }];
+/// assert_eq!(a, "[------]");
+/// ```
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[macro_export]
+macro_rules! string {
+    [$($tt:tt)*] => {
+        ::core::iter::Iterator::collect::<$crate::alloc::string::String>(
+            ::core::iter::IntoIterator::into_iter($crate::vec![$($tt)*]),
+        )
     };
-    [$( $item:expr ),* $(,)?] => {
-        [ $($item),* ]
+}
+
+/// A macro for `HashMap` literals.
+///
+/// It accepts the same `key: value` syntax as the blocks of
+/// [`arr!`](macro.arr.html). Keys that consist of more than one token must be
+/// wrapped in parentheses. If a key appears more than once, the last value
+/// wins.
+///
+/// > This macro requires the **`std`** feature (enabled by default)
+///
+/// # Example
+///
+///```rust
+/// # use array_lit::hash_map;
+/// let a = hash_map! { "a": 1, "b": 2, (" c ".trim()): 3 };
+/// assert_eq!(a["b"], 2);
+/// assert_eq!(a["c"], 3);
+/// ```
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! hash_map {
+    { $( $key:tt : $value:expr ),* $(,)? } => {
+        {
+            #[allow(unused_mut, unused_parens)]
+            {
+                let mut map = $crate::__HashMap::new();
+                $( map.insert($key, $value); )*
+                map
+            }
+        }
     };
+}
 
-    // Implementation details:
-    (impl $arr:ident { [$start:tt] : [ $value:expr ; $len:expr ] }) => {
-        let mut i = $start;
-        let end = i + $len;
-        while i < end {
-            $arr[i] = $value;
-            i += 1;
+/// A macro for `BTreeMap` literals.
+///
+/// This accepts the same syntax as [`hash_map!`](macro.hash_map.html). The
+/// entries of a `BTreeMap` are sorted by their key, regardless of the order
+/// in which they are inserted.
+///
+/// > This macro requires the **`std`** feature (enabled by default) or the
+/// > **`alloc`** feature
+///
+/// # Example
+///
+///```rust
+/// # use array_lit::btree_map;
+/// let a = btree_map! { 3: "c", 1: "a" };
+/// assert_eq!(a.into_values().collect::<Vec<_>>(), ["a", "c"]);
+/// ```
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[macro_export]
+macro_rules! btree_map {
+    { $( $key:tt : $value:expr ),* $(,)? } => {
+        {
+            #[allow(unused_mut, unused_parens)]
+            {
+                let mut map = $crate::alloc::collections::BTreeMap::new();
+                $( map.insert($key, $value); )*
+                map
+            }
         }
     };
-    (impl $arr:ident { [$start:tt] : [ $($value:expr),* $(,)? ] }) => {
-        let mut i = $start;
-        $(
-            $arr[i] = $value;
-            i += 1;
-        )*
+}
+
+/// A macro for `VecDeque` literals with superpowers.
+///
+/// This accepts the same syntax as [`vec!`](macro.vec.html), but returns a
+/// `VecDeque`.
+///
+/// > This macro requires the **`std`** feature (enabled by default) or the
+/// > **`alloc`** feature
+///
+/// # Example
+///
+///```rust
+/// # use array_lit::vec_deque;
+/// let a = vec_deque![0; 8; { 3: 1, [5]: [2, 3] }];
+/// assert_eq!(a, [0, 0, 0, 1, 0, 2, 3, 0]);
+/// ```
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[macro_export]
+macro_rules! vec_deque {
+    [$item:expr ; $len:literal ; { $($body:tt)* }] => {
+        {
+            #[allow(unused_mut, unused_assignments, unused_parens)]
+            {
+                $crate::arr!(impl @check $len { $($body)* });
+                let mut deque = $crate::alloc::collections::VecDeque::from(
+                    $crate::alloc::vec![$item ; $len],
+                );
+                $crate::arr!(impl deque @block { $($body)* });
+                deque
+            }
+        }
     };
-    (impl $arr:ident { [$start:tt] : $value:expr }) => {
-        let mut i = $start;
-        let start = i;
-        let arr_inner = $value;
-        let end = i + arr_inner.len();
-        while i < end {
-            $arr[i] = arr_inner[i - start];
-            i += 1;
+    [$item:expr ; $len:expr ; { $($body:tt)* }] => {
+        {
+            #[allow(unused_mut, unused_assignments, unused_parens)]
+            {
+                let mut deque = $crate::alloc::collections::VecDeque::from(
+                    $crate::alloc::vec![$item ; $len],
+                );
+                $crate::arr!(impl deque @block { $($body)* });
+                deque
+            }
         }
     };
-    (impl $arr:ident { $key:tt : $value:expr }) => {
-        $arr[$key] = $value;
+
+    [$($tt:tt)*] => {
+        $crate::alloc::collections::VecDeque::from($crate::vec![$($tt)*])
     };
 }
 
-/// A macro for `Vec` literals with superpowers.
+/// A macro for `SmallVec` literals with superpowers.
 ///
-/// See [the module level documentation](index.html) for more.
+/// This accepts the same syntax as [`vec!`](macro.vec.html), but returns a
+/// `SmallVec` from the [`smallvec`](https://docs.rs/smallvec) crate. The
+/// inline capacity is inferred from the type annotation.
 ///
-/// > This macro requires the **`std`** feature (enabled by default)
+/// > This macro requires the **`smallvec`** feature
 ///
 /// # Example
 ///
 ///```rust
-/// # use array_lit::vec;
-/// let a = vec![1; 5; { [0]: [1, 2], 4: 0 }];
-/// assert_eq!(a, std::vec![1, 2, 1, 1, 0]);
+/// use array_lit::small_vec;
+/// use smallvec::SmallVec;
+///
+/// let a: SmallVec<[i32; 8]> = small_vec![0; 4; { 1: 1, ^0: 3 }];
+/// assert_eq!(&a[..], [0, 1, 0, 3]);
+/// assert!(!a.spilled());
 /// ```
-#[cfg(feature = "std")]
+#[cfg(feature = "smallvec")]
 #[macro_export]
-macro_rules! vec {
-    [$item:expr ; $len:expr ; { $( $index:tt : $value:expr ),* $(,)? }] => {
+macro_rules! small_vec {
+    [$item:expr ; $len:expr ; { $($body:tt)* }] => {
         {
-            #[allow(unused_mut, unused_assignments)]
+            #[allow(unused_mut, unused_assignments, unused_parens)]
             {
-                let mut vec = std::vec![$item ; $len];
-                $( $crate::arr!(impl vec { $index : $value }); )*
+                let mut vec = $crate::__smallvec::smallvec![$item ; $len];
+                $crate::arr!(impl vec @block { $($body)* });
                 vec
             }
         }
@@ -276,9 +5651,9 @@ macro_rules! vec {
 
     // same syntax as regular array literals:
     [$item:expr ; $len:expr] => {
-        std::vec![$item ; $len]
+        $crate::__smallvec::smallvec![$item ; $len]
     };
     [$( $item:expr ),* $(,)?] => {
-        std::vec![ $($item),* ]
+        $crate::__smallvec::smallvec![ $($item),* ]
     };
 }
@@ -0,0 +1,103 @@
+//! A serializable representation of arrays where only some elements are
+//! specified, like in [`arr!`](../macro.arr.html).
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::IndexError;
+
+/// An array as a default value and the elements that differ from it.
+///
+/// In JSON, this looks like `{"default": 0, "overrides": {"3": 1}}`. The
+/// `overrides` can be omitted if there are none.
+///
+/// ```
+/// use array_lit::{arr, sparse::SparseArray};
+///
+/// let sparse: SparseArray<i32> = serde_json::from_str(r#"{
+///     "default": 0,
+///     "overrides": { "3": 1, "5": 2 }
+/// }"#).unwrap();
+/// assert_eq!(sparse.to_array(), Ok(arr![0; 6; { 3: 1, 5: 2 }]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SparseArray<T> {
+    /// The value of every element that isn't overridden
+    pub default: T,
+    /// The elements that differ from the default, by index
+    #[serde(default = "BTreeMap::new")]
+    pub overrides: BTreeMap<usize, T>,
+}
+
+impl<T: Clone + PartialEq> SparseArray<T> {
+    /// Creates the sparse representation of `slice`, where every element that
+    /// isn't equal to `default` is an override.
+    pub fn from_slice(default: T, slice: &[T]) -> Self {
+        let overrides = slice
+            .iter()
+            .enumerate()
+            .filter(|&(_, value)| *value != default)
+            .map(|(i, value)| (i, value.clone()))
+            .collect();
+        SparseArray { default, overrides }
+    }
+}
+
+impl<T: Clone> SparseArray<T> {
+    /// Creates an array with the default value and applies the overrides.
+    ///
+    /// Returns an error if an override is out of bounds.
+    pub fn to_array<const N: usize>(&self) -> Result<[T; N], IndexError> {
+        let mut arr: [T; N] = crate::__clone_array(self.default.clone());
+        self.apply(&mut arr)?;
+        Ok(arr)
+    }
+
+    /// Creates a `Vec` with the default value and applies the overrides.
+    ///
+    /// Returns an error if an override is out of bounds.
+    pub fn to_vec(&self, len: usize) -> Result<Vec<T>, IndexError> {
+        let mut vec = alloc::vec![self.default.clone(); len];
+        self.apply(&mut vec)?;
+        Ok(vec)
+    }
+
+    fn apply(&self, slice: &mut [T]) -> Result<(), IndexError> {
+        let len = slice.len();
+        for (&index, value) in &self.overrides {
+            match slice.get_mut(index) {
+                Some(element) => *element = value.clone(),
+                None => return Err(IndexError { index, len }),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Deserializes an array from a [`SparseArray`]. Use it with
+/// `#[serde(deserialize_with = "array_lit::sparse::deserialize")]`.
+///
+/// An override that is out of bounds is an error.
+pub fn deserialize<'de, D, T, const N: usize>(deserializer: D) -> Result<[T; N], D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Clone,
+{
+    SparseArray::<T>::deserialize(deserializer)?
+        .to_array()
+        .map_err(D::Error::custom)
+}
+
+/// Serializes an array as a [`SparseArray`] with `T::default()` as the
+/// default. Use it with
+/// `#[serde(serialize_with = "array_lit::sparse::serialize")]`.
+pub fn serialize<S, T, const N: usize>(arr: &[T; N], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize + Clone + PartialEq + Default,
+{
+    SparseArray::from_slice(T::default(), arr).serialize(serializer)
+}
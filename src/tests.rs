@@ -1,6 +1,16 @@
-use crate::arr;
+use core::cell::Cell;
+
+use crate::builder::ArrayBuilder;
+use crate::iter::OverrideIter;
 #[cfg(feature = "std")]
-use crate::vec;
+use crate::{arc_arr, arr_dbg, boxed_arr, hash_map, rc_arr, string, try_vec, vec, vec_deque};
+use crate::{
+    arr, arr2d, arr2d_flat, arr3d, arr_concat, arr_from_slice, arr_range, arr_strict,
+    arr_transpose, arr_uninit, const_arr, fill_from_slice, fill_range, repeat_pattern, try_arr,
+    zeroed_arr, IndexError, SliceLengthError, TooFewItemsError,
+};
+#[cfg(any(feature = "std", feature = "alloc"))]
+use crate::{arr_traced, btree_map};
 
 #[test]
 fn test_simple_literals() {
@@ -8,6 +18,39 @@ fn test_simple_literals() {
     assert_eq!(arr![5, 4, 3, 2, 1], [5, 4, 3, 2, 1]);
 }
 
+#[test]
+fn test_destructuring() {
+    let [a, b, c] = arr![7; 3];
+    assert_eq!((a, b, c), (7, 7, 7));
+    let [a, b] = arr![0; 2; { 0: 1 }];
+    assert_eq!((a, b), (1, 0));
+    let [first, .., last] = arr![0; 6; { 1..5: 2, ^0: 3 }];
+    assert_eq!((first, last), (0, 3));
+    let [x, [y, z]] = arr![[0; 2]; 2; { 0: [1; 2], 1: arr![5; 2; { 1: 6 }] }];
+    assert_eq!((x, y, z), ([1, 1], 5, 6));
+
+    #[cfg(feature = "std")]
+    {
+        let [s, t]: [String; 2] = arr![default; 2; { 1: "b".to_string() }];
+        assert_eq!((s.as_str(), t.as_str()), ("", "b"));
+    }
+    let [p, q] = arr_uninit![2; { 0: 'p', 1: 'q' }];
+    assert_eq!((p, q), ('p', 'q'));
+    const PAIR: [u8; 2] = const_arr![0; 2; { 1: 4 }];
+    let [_, four] = PAIR;
+    assert_eq!(four, 4);
+
+    if let [1, rest @ ..] = arr![0; 3; { 0: 1, 2: 3 }] {
+        assert_eq!(rest, [0, 3]);
+    } else {
+        panic!("the pattern didn't match");
+    }
+    match arr![0; 2; { [0]: [4, 2] }] {
+        [4, n] => assert_eq!(n, 2),
+        _ => panic!("the pattern didn't match"),
+    }
+}
+
 #[test]
 #[cfg(feature = "std")]
 fn test_simple_vec_literals() {
@@ -42,12 +85,13 @@ fn test_runtime_values() {
 }
 
 #[test]
+#[cfg(not(feature = "check-overlap"))]
 fn test_assignment_order() {
     assert_eq!(arr![4; 5; { [0]: [1, 2, 3], 1: 5 }], [1, 5, 3, 4, 4]);
 }
 
 #[test]
-#[cfg(feature = "std")]
+#[cfg(all(feature = "std", not(feature = "check-overlap")))]
 fn test_assignment_order_vec() {
     assert_eq!(
         vec![4; 5; { [0]: [1, 2, 3], 1: 5 }],
@@ -99,3 +143,2314 @@ fn test_custom_index() {
         vec![S(true), S(true), S(true), S(false)]
     );
 }
+
+#[test]
+fn test_range_indices() {
+    assert_eq!(arr![9; 8; { 2..5: 0 }], [9, 9, 0, 0, 0, 9, 9, 9]);
+    assert_eq!(arr![9; 4; { 3..3: 0 }], [9; 4]);
+    assert_eq!(arr![0; 5; { 1: 1, 2..4: 2, 4: 3 }], [0, 1, 2, 2, 3]);
+    let end = 3;
+    assert_eq!(arr![0; 4; { 0..end: 1 }], [1, 1, 1, 0]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_range_indices_vec() {
+    assert_eq!(vec![9; 6; { 2..5: 0 }], std::vec![9, 9, 0, 0, 0, 9]);
+    assert_eq!(vec![9; 4; { 3..3: 0 }], std::vec![9; 4]);
+}
+
+#[test]
+fn test_literal_entries_in_const() {
+    const A: [i32; 6] = arr![0; 6; { 0: 1, 1..3: 2, 3..=4: 3, 5: 4 }];
+    assert_eq!(A, [1, 2, 2, 3, 3, 4]);
+    const B: [u8; 4] = arr![0; 4; { ..2: 1, ..=0: 2 }];
+    assert_eq!(B, [2, 1, 0, 0]);
+    static C: [u8; 3] = arr![0; 3; { 2: 1, 0..1: 2 }];
+    assert_eq!(C, [2, 0, 1]);
+    const D: [i32; 8] = arr![0; 8; {
+        // the first element
+        0: 1,
+        2: 3,
+        // the last element
+        7: 9,
+    }];
+    assert_eq!(D, [1, 0, 3, 0, 0, 0, 0, 9]);
+}
+
+#[test]
+fn test_integer_literal_indices() {
+    let a = arr![0; 0x10; { 0x1: 1, [0b10]: [2, 3], 0o5..0x7: 4, 0b1000..=0o11: 5, ^0x1: 6, 1_1 | 0xC: 7 }];
+    assert_eq!(a, [0, 1, 2, 3, 0, 4, 4, 0, 5, 5, 0, 7, 7, 0, 6, 0]);
+    let b =
+        arr![0; 1_000; { 0x3E7: 1, [0b11_1110_0110]: [2], [0o1_000]: [3; 0b10], 0x3e0usize: 4 }];
+    assert_eq!(b[992..], [4, 0, 0, 0, 0, 0, 2, 1]);
+    assert_eq!(b[512..514], [3, 3]);
+    let c = arr![0; _; { 0x4: 1, [0b1]: [2, 3] }];
+    assert_eq!(c, [0, 2, 3, 0, 1]);
+
+    const D: [u8; 0x20] =
+        const_arr![0; 0x20; { [0x10]: [1, 2], [0b100]: [5; 0o2], 1_0..0x0C: 7, 0x1_F: 1 }];
+    assert_eq!(D[4..6], [5, 5]);
+    assert_eq!(D[10..12], [7, 7]);
+    assert_eq!(D[16..18], [1, 2]);
+    assert_eq!(D[31], 1);
+    let e = arr_uninit![0x3; { 0b0: 'a', 0o1: 'b', 0x2: 'c' }];
+    assert_eq!(e, ['a', 'b', 'c']);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_integer_literal_indices_vec() {
+    let a = vec![0; 0x8; { [0x2]: [1, 2], 0b111: 3, 0o4..0x6: 4, 1_0 % 0x8: 5 }];
+    assert_eq!(a, [0, 0, 5, 2, 4, 4, 0, 3]);
+}
+
+#[test]
+fn test_inclusive_range_indices() {
+    assert_eq!(arr![0; 8; { 2..=4: 7 }], [0, 0, 7, 7, 7, 0, 0, 0]);
+    assert_eq!(arr![0; 4; { 2..=2: 1 }], [0, 0, 1, 0]);
+    assert_eq!(arr![0; 8; { 5..=4: 1 }], [0; 8]);
+    assert_eq!(arr![0; 4; { 0..=3: 1 }], [1; 4]);
+    assert_eq!(arr![0; 4; { ..=0: 1 }], [1, 0, 0, 0]);
+    assert_eq!(arr![0; 4; { ..=2: 1 }], [1, 1, 1, 0]);
+    let end = 3;
+    assert_eq!(arr![0; 4; { ..=end: 1 }], [1; 4]);
+    const C: [i32; 3] = const_arr![0; 3; { ..=1: 1 }];
+    assert_eq!(C, [1, 1, 0]);
+}
+
+#[test]
+#[should_panic]
+fn test_inclusive_range_to_out_of_bounds() {
+    let end = 4;
+    let _ = arr![0; 4; { ..=end: 1 }];
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_inclusive_range_indices_vec() {
+    assert_eq!(vec![0; 5; { 2..=4: 7 }], std::vec![0, 0, 7, 7, 7]);
+    assert_eq!(vec![0; 3; { 2..=1: 7 }], std::vec![0; 3]);
+    assert_eq!(vec![0; 3; { ..=0: 7 }], std::vec![7, 0, 0]);
+    assert_eq!(vec![0; 3; { ..=2: 7 }], std::vec![7; 3]);
+}
+
+#[test]
+fn test_indices_from_end() {
+    assert_eq!(arr![0; 8; { ^0: 9 }], [0, 0, 0, 0, 0, 0, 0, 9]);
+    assert_eq!(arr![0; 4; { ^1: 8, ^3: 7 }], [7, 0, 8, 0]);
+    let k = 2;
+    assert_eq!(arr![0; 4; { ^k: 1 }], [0, 1, 0, 0]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_indices_from_end_vec() {
+    assert_eq!(vec![0; 3; { ^0: 9, ^2: 1 }], std::vec![1, 0, 9]);
+}
+
+#[test]
+#[should_panic(expected = "array-lit: index ^4 is out of bounds for length 4")]
+fn test_indices_from_end_out_of_bounds() {
+    let _ = arr![0; 4; { ^4: 1 }];
+}
+
+#[test]
+#[should_panic(expected = "array-lit: index ^9 is out of bounds for length 4")]
+fn test_compound_index_from_end_out_of_bounds() {
+    let i = 9;
+    let _ = arr![0; 4; { ^i: += 1 }];
+}
+
+#[test]
+fn test_clamped_indices() {
+    assert_eq!(arr![0; 4; { clamp 9: 1 }], [0, 0, 0, 1]);
+    assert_eq!(
+        arr![0; 4; { clamp 4: 1, clamp 3: 2, clamp 1: 3 }],
+        [0, 3, 0, 2]
+    );
+    let (i, k) = (2, 5);
+    assert_eq!(arr![0; 4; { clamp i + k: 1, clamp i: 2 }], [0, 0, 2, 1]);
+    const A: [u8; 2] = const_arr![0; 2; { clamp 100: 1 }];
+    assert_eq!(A, [0, 1]);
+
+    // nothing is assigned in an empty array, and the value isn't computed
+    let mut computed = false;
+    let b: [i32; 0] = arr![0; 0; { clamp 0: { computed = true; 1 } }];
+    assert_eq!(b, []);
+    assert!(!computed);
+}
+
+#[test]
+fn test_wrapping_indices() {
+    let head = 6;
+    let a = arr![0; 8; { wrap (head + 1): 1, wrap (head + 2): 2, wrap (head + 3): 3 }];
+    assert_eq!(a, [2, 3, 0, 0, 0, 0, 0, 1]);
+    assert_eq!(
+        arr![0; 4; { wrap 4: 1, wrap 11: 2, wrap 2: 3 }],
+        [1, 0, 3, 2]
+    );
+    const A: [u8; 3] = const_arr![0; 3; { wrap 3: 1 }];
+    assert_eq!(A, [1, 0, 0]);
+
+    // nothing is assigned in an empty array, and the value isn't computed
+    let mut computed = false;
+    let b: [i32; 0] = arr![0; 0; { wrap head: { computed = true; 1 } }];
+    assert_eq!(b, []);
+    assert!(!computed);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_clamped_indices_vec() {
+    let len = 3;
+    assert_eq!(vec![0; len; { clamp usize::MAX: 1 }], [0, 0, 1]);
+    assert_eq!(vec![0; 0; { clamp 5: 1 }], []);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_wrapping_indices_vec() {
+    let len = 3;
+    assert_eq!(vec![0; len; { wrap (len + 1): 1 }], [0, 1, 0]);
+}
+
+#[test]
+fn test_elements_from_index() {
+    assert_eq!(arr![fn |i| i * i; 5], [0, 1, 4, 9, 16]);
+    assert_eq!(arr![fn |i| i as u8; 0], [0u8; 0]);
+
+    #[derive(PartialEq, Debug)]
+    struct X(usize); // does NOT implement Copy
+
+    assert_eq!(arr![fn X; 3], [X(0), X(1), X(2)]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_elements_from_index_vec() {
+    let n = 4;
+    assert_eq!(vec![fn |i| i + 1; n], std::vec![1, 2, 3, 4]);
+    assert_eq!(
+        vec![fn |i| i.to_string(); 2],
+        std::vec![String::from("0"), String::from("1")]
+    );
+}
+
+#[test]
+fn test_open_range_indices() {
+    assert_eq!(arr![0; 8; { ..3: 1 }], [1, 1, 1, 0, 0, 0, 0, 0]);
+    assert_eq!(arr![0; 8; { 5..: 2 }], [0, 0, 0, 0, 0, 2, 2, 2]);
+    assert_eq!(arr![0; 4; { ..0: 1, 4..: 2 }], [0; 4]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_open_range_indices_vec() {
+    let len = 6;
+    assert_eq!(vec![0; len; { 4..: 2 }], std::vec![0, 0, 0, 0, 2, 2]);
+    assert_eq!(vec![0; len; { ..2: 1 }], std::vec![1, 1, 0, 0, 0, 0]);
+}
+
+#[test]
+fn test_stepped_range_indices() {
+    assert_eq!(arr![0; 8; { 0..8;2: 1 }], [1, 0, 1, 0, 1, 0, 1, 0]);
+    assert_eq!(arr![0; 8; { 1..8;3: 1 }], [0, 1, 0, 0, 1, 0, 0, 1]);
+    assert_eq!(arr![0; 8; { 2..7;4: 1 }], [0, 0, 1, 0, 0, 0, 1, 0]);
+    assert_eq!(arr![0; 4; { 0..4;10: 1 }], [1, 0, 0, 0]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_stepped_range_indices_vec() {
+    assert_eq!(vec![0; 5; { 1..5;2: 1 }], std::vec![0, 1, 0, 1, 0]);
+}
+
+#[test]
+#[should_panic(expected = "array-lit: step of a range index must not be zero")]
+fn test_stepped_range_indices_zero_step() {
+    let _ = arr![0; 4; { 0..4;0: 1 }];
+}
+
+#[test]
+fn test_index_closure_entries() {
+    // the closure receives the index, not the number of the iteration
+    let a = arr![0; 16; { 0..16;2: fn |i| i * 10 }];
+    assert_eq!(
+        a,
+        [0, 0, 20, 0, 40, 0, 60, 0, 80, 0, 100, 0, 120, 0, 140, 0]
+    );
+    let b = arr![0; 8; { 1..8;3: fn |i| i + 1, 6..: fn |i| i * 2, 0 | 2: fn |i| i + 100 }];
+    assert_eq!(b, [100, 2, 102, 0, 5, 0, 12, 14]);
+    let c = arr![0; 4; { ..=1: fn |i| i + 1, 3: fn |i| i }];
+    assert_eq!(c, [1, 2, 0, 3]);
+
+    let mut order = [0; 3];
+    let mut n = 0;
+    let d = arr![0; 6; { rev 0..6;2: fn |i| { order[n] = i; n += 1; i } }];
+    assert_eq!(d, [0, 0, 2, 0, 4, 0]);
+    assert_eq!(order, [4, 2, 0]);
+    let e = arr![1; 4; as a { 0: 5, 1..4: fn |i| i * 3, 3: a[1] + 1 }];
+    assert_eq!(e, [5, 3, 6, 4]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_index_closure_entries_vec() {
+    let len = 5;
+    let a = vec![String::new(); len; { 1..len;2: fn |i| i.to_string() }];
+    assert_eq!(a, ["", "1", "", "3", ""]);
+    assert_eq!(vec![0; 4; { in [3, 1]: fn |i| i * i }], [0, 1, 0, 9]);
+}
+
+#[test]
+fn test_guarded_entries() {
+    fn is_prime(n: usize) -> bool {
+        matches!(n, 2 | 3 | 5 | 7)
+    }
+    // only the indices where the guard holds are written
+    let a = arr![0; 8; { 0..8 where is_prime: 1 }];
+    assert_eq!(a, [0, 0, 1, 1, 0, 1, 0, 1]);
+    let b = arr![0; 8; { 0..8 where |i| i % 3 == 0: 7, 1: 2 }];
+    assert_eq!(b, [7, 2, 0, 7, 0, 0, 7, 0]);
+    let c = arr![0; 8; { 2.. where |i| i % 2 == 1: fn |i| i * 10 }];
+    assert_eq!(c, [0, 0, 0, 30, 0, 50, 0, 70]);
+    let d = arr![0; 8; { 0..8;2 where |i| i > 2: 1, rev ..4 where |i| i < 2: 2, 5 | 6 where |i| i == 6: 3 }];
+    assert_eq!(d, [2, 2, 0, 0, 1, 0, 3, 0]);
+    let e = arr![1; 6; as a { 0: 5, 1..6 where |i| i != 3: a[0] + 1 }];
+    assert_eq!(e, [5, 6, 6, 1, 6, 6]);
+
+    // the guard is called for every index, in order
+    let mut seen = [false; 4];
+    let f = arr![0; 4; { 1..4 where |i| { seen[i] = true; false }: 1 }];
+    assert_eq!(f, [0; 4]);
+    assert_eq!(seen, [false, true, true, true]);
+}
+
+#[test]
+fn test_element_guards() {
+    // only the slots that are still zero are replaced
+    let n = 6;
+    let a = arr![0; 6; { 1: 5, 3: 7, 0..n if |x| *x == 0: 1 }];
+    assert_eq!(a, [1, 5, 1, 7, 1, 1]);
+    let b = arr![0; 6; { 1 | 4: 2, 0..6 if |x| *x > 0: fn |i| i * 10 }];
+    assert_eq!(b, [0, 10, 0, 0, 40, 0]);
+    let c = arr![1; 4; as a { 0: 0, 0..4 if |x| *x != 0: a[0] + 3 }];
+    assert_eq!(c, [0, 3, 3, 3]);
+
+    // every element is checked before it is written
+    let d = arr![0; 5; { 0: 1, 1..5 if |x| *x == 0: 1, 0..5 if |x| *x == 1: 2 }];
+    assert_eq!(d, [2; 5]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_guarded_entries_vec() {
+    let a = vec![String::new(); 4; { 0..4 where |i| i != 1: String::from("x") }];
+    assert_eq!(a, ["x", "", "x", "x"]);
+    let b =
+        vec![String::new(); 3; { 1: "a".to_string(), 0..3 if |s| s.is_empty(): String::from("-") }];
+    assert_eq!(b, ["-", "a", "-"]);
+    let c =
+        vec![String::new(); 4; { 0: "a".into(), 1..4 where |i| i != 2: |v| v[0].clone() + "b" }];
+    assert_eq!(c, ["a", "ab", "", "ab"]);
+    let d =
+        arr![each String::new(); 3; { 0: "x".into(), 0..3 if |s| s.is_empty(): |a| a[0].clone() }];
+    assert_eq!(d, ["x", "x", "x"]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_omitted_fill_value() {
+    // the element type is neither `Copy` nor `Default`
+    #[derive(Debug, PartialEq)]
+    struct Label(String);
+
+    let a = arr![_; 5; { ..: fn |i| Label(format!("#{}", i)) }];
+    assert_eq!(a[0], Label("#0".to_string()));
+    assert_eq!(a[4], Label("#4".to_string()));
+
+    let b = arr![_; 4; { ..: fn |i| vec![i; i], 1..3: vec![], 3: vec![7] }];
+    assert_eq!(b, [vec![], vec![], vec![], vec![7]]);
+
+    let mut calls = 0;
+    let c = arr![_; 3; { ..: { calls += 1; String::from("x") }, 2: String::new() }];
+    assert_eq!(c, ["x", "x", ""]);
+    assert_eq!(calls, 3);
+}
+
+#[test]
+fn test_reverse_range_indices() {
+    // a full sweep visits every index from the end down to 0
+    let mut n = 0;
+    let a = arr![0; 8; { rev 0..8: { n += 1; n } }];
+    assert_eq!(a, [8, 7, 6, 5, 4, 3, 2, 1]);
+    let mut n = 0;
+    let b = arr![0; 8; { rev ..=7: { n += 1; n } }];
+    assert_eq!(b, a);
+
+    // with a step, the same elements as in the forward range are set
+    let mut n = 0;
+    let c = arr![0; 8; { rev 1..8;3: { n += 1; n } }];
+    assert_eq!(c, [0, 3, 0, 0, 2, 0, 0, 1]);
+    let mut n = 0;
+    let d = arr![0; 8; { rev 2..=5: { n += 1; n }, rev 6..6: 9 }];
+    assert_eq!(d, [0, 0, 4, 3, 2, 1, 0, 0]);
+
+    let start = 1;
+    let e = arr![0; 4; { rev start..(start + 2): 1 }];
+    assert_eq!(e, [0, 1, 1, 0]);
+    const F: [i32; 4] = const_arr![0; 4; { rev 0..4;2: 1 }];
+    assert_eq!(F, [1, 0, 1, 0]);
+}
+
+#[test]
+fn test_compound_assignment() {
+    let a = arr![10; 6; { 3: += 5, 0: 1, 0: *= 3, 1..=2: -= 4, 4 | 5: <<= 1, ^0: += 1 }];
+    assert_eq!(a, [3, 6, 6, 15, 20, 21]);
+    let x = 3;
+    let b = arr![1; 4; { x - 1: += x, (x): |= 4, ..2: *= 7 }];
+    assert_eq!(b, [7, 7, 4, 5]);
+    let c = arr![1; 4; { 3: 5, 0: += |a| a[3], 1: *= |a| a[0] }];
+    assert_eq!(c, [6, 6, 1, 5]);
+    let d = arr![1; 4; as a { 1: 5, 2: += a[1], 3: a[2] * 2, 0: *= |b| b[3] }];
+    assert_eq!(d, [12, 5, 6, 12]);
+    let i = 9;
+    assert_eq!(
+        try_arr![0; 4; { ^i: += 1 }],
+        Err(IndexError { index: 9, len: 4 })
+    );
+    const C: [i32; 4] = const_arr![1; 4; { 0: += 2, 1..3: *= 5, ^0: -= 1 }];
+    assert_eq!(C, [3, 5, 5, 0]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_compound_assignment_vec() {
+    let a = vec![2; 4; { ..2: += 1, 2..: *= 2, in [0, 3]: += 10 }];
+    assert_eq!(a, [13, 3, 4, 14]);
+    let b = vec![String::from("a"); 2; { 1: += "b" }];
+    assert_eq!(b, ["a", "ab"]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_reverse_range_indices_vec() {
+    let (mut n, len) = (0, 5);
+    let a = vec![0; len; { rev 0..len: { n += 1; n } }];
+    assert_eq!(a, [5, 4, 3, 2, 1]);
+    assert_eq!(vec![0; auto; { rev 0..=2: 1 }], [1, 1, 1]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_boxed_arr() {
+    let a: Box<[i32]> = boxed_arr![3; 5];
+    assert_eq!(*a, [3; 5]);
+    assert_eq!(*boxed_arr![1, 2, 3], [1, 2, 3]);
+    assert_eq!(
+        *boxed_arr![0; 6; { [0]: [1, 2], 4: 4, 5..: 5 }],
+        [1, 2, 0, 0, 4, 5]
+    );
+    assert_eq!(boxed_arr![0; 1_000_000; { ^0: 1 }][999_999], 1);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_shared_arr() {
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    let a: Arc<[i32]> = arc_arr![0; 8; { 3: 1 }];
+    assert_eq!(*a, [0, 0, 0, 1, 0, 0, 0, 0]);
+    assert_eq!(Arc::strong_count(&a), 1);
+    let b: Rc<[i32]> = rc_arr![0; 6; { [0]: [1, 2], 4: 4, 5..: 5 }];
+    assert_eq!(*b, [1, 2, 0, 0, 4, 5]);
+    assert_eq!(Rc::strong_count(&b), 1);
+    assert_eq!(*arc_arr![1, 2, 3], [1, 2, 3]);
+    assert_eq!(*rc_arr![String::from("a"); 2], ["a", "a"]);
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "alloc"))]
+fn test_traced() {
+    let (a, indices) = arr_traced![0; 6; { 4: 1, 1..3: 2 }];
+    assert_eq!(a, [0, 2, 2, 0, 1, 0]);
+    assert_eq!(indices, [4, 1, 2]);
+
+    // ranges and slices are expanded to every index they assign
+    let src = [7, 8];
+    let (a, indices) = arr_traced![0; 10; { [6]: src, 0..=2: 1, 4..8;2: 3, ..0: 9 }];
+    assert_eq!(a, [1, 1, 1, 0, 3, 0, 3, 8, 0, 0]);
+    assert_eq!(indices, [6, 7, 0, 1, 2, 4, 6]);
+    let (a, indices) = arr_traced![0; 5; { [1]: [1, 2] * 2, 0 | 4: 3, 3..5: from 1 }];
+    assert_eq!(a, [3, 1, 2, 1, 2]);
+    assert_eq!(indices, [1, 2, 3, 4, 0, 4, 3, 4]);
+
+    // compound assignments and closures are traced as well
+    let (a, indices) = arr_traced![1; 3; { 2: += 1, 0: |a| a[2] * 2 }];
+    assert_eq!(a, [4, 1, 2]);
+    assert_eq!(indices, [2, 0]);
+    let (_, indices) = arr_traced![0; 3; {}];
+    assert!(indices.is_empty());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_dbg() {
+    let a = arr_dbg![0; 8; { 3: 1 }];
+    assert_eq!(a, [0, 0, 0, 1, 0, 0, 0, 0]);
+
+    // non-`Copy` elements are moved out, not cloned
+    let a = arr_dbg!["a".to_string(), "b".to_string()];
+    assert_eq!(a, ["a", "b"]);
+    let a: [u8; 3] = arr_dbg![1, 2, 3];
+    assert_eq!(a, [1, 2, 3]);
+}
+
+#[test]
+fn test_overlap_detection() {
+    use crate::__has_overlap;
+
+    assert!(!__has_overlap(&[]));
+    assert!(!__has_overlap(&[(0, 2), (2, 4), (5, 6)]));
+    assert!(!__has_overlap(&[(0, 4), (2, 2)]));
+    assert!(__has_overlap(&[(0, 3), (1, 2)]));
+    assert!(__has_overlap(&[(4, 5), (0, 2), (3, 5)]));
+
+    // compiles with the `check-overlap` feature, since nothing overlaps
+    assert_eq!(
+        arr![0; 8; { [0]: [1, 2], 2: 3, 3..5: 4, 5..=6: 5, [7]: [6; 1] }],
+        [1, 2, 3, 4, 4, 5, 5, 6]
+    );
+}
+
+#[test]
+fn test_slice_copy() {
+    let src = [7; 1000];
+    let a = arr![0; 1024; { [24]: src }];
+    assert!(a[..24].iter().all(|&x| x == 0));
+    assert!(a[24..].iter().all(|&x| x == 7));
+
+    let src: &[i32] = &[1, 2];
+    assert_eq!(arr![0; 4; { [2]: src }], [0, 0, 1, 2]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_slice_copy_fallback() {
+    use crate::{__CopyByIndex, __Dest};
+    use std::collections::VecDeque;
+
+    let mut deque: VecDeque<_> = std::vec![0; 4].into();
+    (&mut __Dest(&mut deque)).copy_from(1, &[1, 2]);
+    assert_eq!(deque, [0, 1, 2, 0]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_vec_capacity() {
+    let a = vec![0; 8 cap 64; { 3: 1 }];
+    assert_eq!(a, [0, 0, 0, 1, 0, 0, 0, 0]);
+    assert_eq!(a.len(), 8);
+    assert!(a.capacity() >= 64);
+    let (len, cap) = (3, 10);
+    let b: Vec<String> = vec![String::new(); len cap cap];
+    assert_eq!(b.len(), 3);
+    assert!(b.capacity() >= 10);
+    // a capacity smaller than the length is ignored
+    let c = vec![1; 4 cap 1; { ^0: 2 }];
+    assert_eq!(c, [1, 1, 1, 2]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_vec_from_base() {
+    let base = std::vec![String::from("a"), String::from("b"), String::from("c")];
+    let copy = vec![from base; { 1: String::from("x"), [2]: [String::new()] }];
+    assert_eq!(copy, ["a", "x", ""]);
+    assert_eq!(base, ["a", "b", "c"]);
+
+    // arrays and slices can be used as well
+    let base = [0; 4];
+    assert_eq!(vec![from base; { 1..3: 1 }], [0, 1, 1, 0]);
+    assert_eq!(vec![from &base[1..]; { ^0: 2 }], [0, 0, 2]);
+    assert_eq!(base, [0; 4]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+#[should_panic(expected = "index out of bounds")]
+fn test_vec_from_base_out_of_bounds() {
+    let base = std::vec![0; 3];
+    let _ = vec![from base; { 3: 1 }];
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_vec_auto_len() {
+    let src = [1, 2, 3];
+    assert_eq!(vec![0; auto; { [0]: src }], [1, 2, 3]);
+    let src = std::vec![1; 5];
+    assert_eq!(vec![0; auto; { [2]: &src[1..] }], [0, 0, 1, 1, 1, 1]);
+    let empty: Vec<i32> = vec![0; auto; {}];
+    assert!(empty.is_empty());
+
+    // disjoint blocks: the gap is filled with the item
+    let (a, b) = ([1, 2], [3, 4, 5]);
+    assert_eq!(vec![0; auto; { [5]: b, [1]: a }], [0, 1, 2, 0, 0, 3, 4, 5]);
+    // overlapping blocks: the longest one determines the length, and later
+    // entries overwrite earlier ones
+    let (a, b) = ([1, 1, 1, 1], [2, 2]);
+    assert_eq!(vec![0; auto; { [0]: a, [1]: b }], [1, 2, 2, 1]);
+    assert_eq!(vec![0; auto; { [1]: b, [0]: a }], [1, 1, 1, 1]);
+
+    // other entries also count towards the length
+    let s = String::from("x");
+    let v = vec![s; auto; { 3: String::from("y"), [4]: [String::new()] }];
+    assert_eq!(v, ["x", "x", "x", "y", ""]);
+    assert_eq!(vec![0; auto; { 1..=2: 7, 4 | 6: 1 }], [0, 7, 7, 0, 1, 0, 1]);
+    assert_eq!(vec![0; auto; { [1]: [5, 6] * 2 }], [0, 5, 6, 5, 6]);
+    assert_eq!(vec![0; auto; { from_pairs [(3, 1)] }], [0, 0, 0, 1]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_vec_deque() {
+    use std::collections::VecDeque;
+
+    let a: VecDeque<i32> = vec_deque![3; 5];
+    assert_eq!(a, [3; 5]);
+    assert_eq!(vec_deque![1, 2, 3], [1, 2, 3]);
+    assert_eq!(
+        vec_deque![0; 8; { 3: 1, [5]: [2, 3] }],
+        [0, 0, 0, 1, 0, 2, 3, 0]
+    );
+    let len = 5;
+    let src = [4, 5];
+    assert_eq!(
+        vec_deque![0; len; { 1..3: 1, [3]: src, ^0: 9 }],
+        [0, 1, 1, 4, 9]
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_string() {
+    assert_eq!(string!['-'; 8; { 0: '[', 7: ']' }], "[------]");
+    assert_eq!(string!['a', 'b', 'c'], "abc");
+    assert_eq!(string!['x'; 0], "");
+    let len = 5;
+    assert_eq!(string!['.'; len; { [1]: ['o', 'k'], ^0: '!' }], ".ok.!");
+    // indices count characters, not bytes
+    assert_eq!(string!['─'; 5; { 0: '├', 2: 'x', ^0: '┤' }], "├─x─┤");
+    assert_eq!(string!['a'; 3; { 1: 'ß' }], "aßa");
+}
+
+#[test]
+fn test_2d_arrays() {
+    assert_eq!(arr2d![1; 2, 3], [[1; 3]; 2]);
+    assert_eq!(
+        arr2d![0; 3, 4; { (1, 2): 9 }],
+        [[0, 0, 0, 0], [0, 0, 9, 0], [0, 0, 0, 0]]
+    );
+    assert_eq!(
+        arr2d![0; 2, 3; { (0,): [1, 2, 3], (0, 1): 5, (1, 0): 4 }],
+        [[1, 5, 3], [4, 0, 0]]
+    );
+    let row = 1;
+    assert_eq!(arr2d![0; 2, 2; { (row, row - 1): 1 }], [[0, 0], [1, 0]]);
+
+    let empty: [[i32; 4]; 0] = arr2d![0; 0, 4; {}];
+    assert!(empty.is_empty());
+    let empty: [[i32; 0]; 2] = arr2d![0; 2, 0];
+    assert_eq!(empty, [[], []]);
+}
+
+#[test]
+fn test_2d_array_columns() {
+    assert_eq!(
+        arr2d![0; 3, 4; { col 2: [1, 2, 3] }],
+        [[0, 0, 1, 0], [0, 0, 2, 0], [0, 0, 3, 0]]
+    );
+    assert_eq!(
+        arr2d![0; 3, 4; { (1,): [5; 4], col 0: [1, 2, 3,], (2, 0): 9, col 3: [7, 8, 9] }],
+        [[1, 0, 0, 7], [2, 5, 5, 8], [9, 0, 0, 9]]
+    );
+    const ROWS: usize = 2;
+    let col = 1;
+    assert_eq!(arr2d![0; ROWS, 2; { col col: [1, 2] }], [[0, 1], [0, 2]]);
+}
+
+#[test]
+#[should_panic(expected = "array-lit: the column has 1 values, but there are 2 rows")]
+fn test_2d_array_column_wrong_len() {
+    const ROWS: usize = 2;
+    let _ = arr2d![0; ROWS, 2; { col 0: [1] }];
+}
+
+#[test]
+fn test_3d_array() {
+    let a = arr3d![0; 2, 2, 2; { (1, 0, 1): 9 }];
+    let mut cells = 0;
+    for (x, plane) in a.iter().enumerate() {
+        for (y, row) in plane.iter().enumerate() {
+            for (z, &cell) in row.iter().enumerate() {
+                let expected = if (x, y, z) == (1, 0, 1) { 9 } else { 0 };
+                assert_eq!(cell, expected);
+                cells += 1;
+            }
+        }
+    }
+    assert_eq!(cells, 8);
+
+    // the dimensions can differ, and are indexed as `arr[x][y][z]`
+    let (x, y) = (2, 0);
+    let b = arr3d![0; 3, 1, 2; { (0, 0, 0): 1, (x, y, x - 1): 2, }];
+    assert_eq!(b, [[[1, 0]], [[0, 0]], [[0, 2]]]);
+    assert_eq!(arr3d![7; 1, 2, 1], [[[7], [7]]]);
+}
+
+#[test]
+fn test_array_builder() {
+    let a = ArrayBuilder::new(0)
+        .set(0, 1)
+        .set_range(1..3, 2)
+        .set_range(3..=4, 3)
+        .set(5, 4)
+        .build();
+    assert_eq!(a, arr![0; 6; { 0: 1, 1..3: 2, 3..=4: 3, 5: 4 }]);
+    let b = ArrayBuilder::new(9)
+        .set_range(..2, 1)
+        .set_range(6.., 2)
+        .set_range(3..3, 0)
+        .build();
+    assert_eq!(b, arr![9; 8; { ..2: 1, 6..: 2, 3..3: 0 }]);
+
+    // later calls override earlier ones, including `fill`
+    let c = ArrayBuilder::new(1)
+        .set(2, 5)
+        .fill(3)
+        .set_range(.., 4)
+        .set(1, 0)
+        .build();
+    assert_eq!(c, arr![4; 4; { 1: 0 }]);
+
+    let d = ArrayBuilder::from([1, 2, 3]).set(1, 7).build();
+    assert_eq!(d, arr![0; 3; { 0: 1, 1: 7, 2: 3 }]);
+    let e: [Option<u8>; 3] = ArrayBuilder::default().set(2, Some(1)).build();
+    assert_eq!(e, arr![None; 3; { 2: Some(1) }]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_array_builder_non_copy() {
+    let mut builder = ArrayBuilder::new(String::new());
+    for (i, s) in ["a", "b"].iter().enumerate() {
+        builder = builder.set(i * 2, s.to_string());
+    }
+    let a: [String; 4] = builder.set_range(3.., "c".to_string()).build();
+    assert_eq!(
+        a,
+        arr![default; 4; { 0: "a".to_string(), 2: "b".to_string(), 3: "c".to_string() }]
+    );
+}
+
+#[test]
+#[should_panic(expected = "out of range")]
+fn test_array_builder_out_of_bounds() {
+    let _: [u8; 4] = ArrayBuilder::new(0).set_range(2..5, 1).build();
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_override_iter() {
+    use std::iter::repeat;
+
+    let overrides = [(0, 4), (3, 1), (3, 2), (9, 5)];
+    let lazy: Vec<i32> = OverrideIter::new(std::vec![0; 10].into_iter(), overrides).collect();
+    assert_eq!(lazy, vec![0; 10; { 0: 4, 3: 2, 9: 5 }]);
+
+    let lazy: Vec<i32> = OverrideIter::new(repeat(0), overrides).take(4).collect();
+    assert_eq!(lazy, vec![0; 4; { 0: 4, 3: 2 }]);
+
+    let lazy: Vec<u8> = OverrideIter::new(0..5, [(1, 9), (7, 9)]).collect();
+    assert_eq!(lazy, std::vec![0, 9, 2, 3, 4]);
+}
+
+#[test]
+fn test_override_iter_nth() {
+    let mut iter = OverrideIter::new(0..10, [(2, 20), (5, 50), (6, 60)]);
+    assert_eq!(iter.len(), 10);
+    assert_eq!(iter.nth(2), Some(20));
+    assert_eq!(iter.next(), Some(3));
+    assert_eq!(iter.nth(1), Some(50));
+    assert_eq!(iter.nth(1), Some(7));
+    assert_eq!(iter.len(), 2);
+    assert_eq!(iter.nth(2), None);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn test_override_iter_unsorted() {
+    let mut iter = OverrideIter::new(0..4, [(2, 20), (1, 10), (3, 30)]);
+    assert_eq!(iter.next(), Some(0));
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.next(), Some(20));
+    assert_eq!(iter.next(), Some(30));
+}
+
+#[test]
+fn test_fill_range() {
+    let mut a = [0; 6];
+    fill_range(&mut a, 2..5, 7);
+    assert_eq!(a, [0, 0, 7, 7, 7, 0]);
+    fill_range(&mut a, 0..6, 1);
+    assert_eq!(a, [1; 6]);
+    fill_range(&mut a, 6..6, 2);
+    assert_eq!(a, [1; 6]);
+    fill_range(&mut a[1..], 0..2, 3);
+    assert_eq!(a, [1, 3, 3, 1, 1, 1]);
+}
+
+#[test]
+#[should_panic(expected = "out of range")]
+fn test_fill_range_out_of_bounds() {
+    fill_range(&mut [0; 4], 2..5, 1);
+}
+
+#[test]
+#[should_panic(expected = "slice index starts at 3 but ends at 2")]
+fn test_fill_range_reversed() {
+    #[allow(clippy::reversed_empty_ranges)]
+    fill_range(&mut [0; 4], 3..2, 1);
+}
+
+#[test]
+fn test_fill_from_slice() {
+    let mut a = [0; 6];
+    fill_from_slice(&mut a, 3, &[1, 2]);
+    assert_eq!(a, [0, 0, 0, 1, 2, 0]);
+    fill_from_slice(&mut a, 0, &[4; 6]);
+    assert_eq!(a, [4; 6]);
+    fill_from_slice(&mut a, 6, &[]);
+    assert_eq!(a, [4; 6]);
+}
+
+#[test]
+#[should_panic(expected = "out of range")]
+fn test_fill_from_slice_out_of_bounds() {
+    fill_from_slice(&mut [0; 4], 3, &[1, 2]);
+}
+
+#[test]
+fn test_2d_arrays_flat() {
+    assert_eq!(
+        arr2d_flat![0; 2, 3; [1, 2, 3, 4, 5, 6]],
+        [[1, 2, 3], [4, 5, 6]]
+    );
+    assert_eq!(
+        arr2d_flat![0; 3, 2; [1, 2, 3, 4, 5, 6,]],
+        [[1, 2], [3, 4], [5, 6]]
+    );
+    assert_eq!(arr2d_flat![0; 1, 1; [7]], [[7]]);
+    const ROWS: usize = 2;
+    let a = arr2d_flat![""; ROWS, 2; ["a", "b", "c", "d"]];
+    assert_eq!(a, [["a", "b"], ["c", "d"]]);
+    let empty: [[i32; 3]; 0] = arr2d_flat![0; 0, 3; []];
+    assert!(empty.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "array-lit: 3 values were given, but 2 * 2 were expected")]
+fn test_2d_arrays_flat_wrong_count() {
+    const ROWS: usize = 2;
+    let _ = arr2d_flat![0; ROWS, 2; [1, 2, 3]];
+}
+
+#[test]
+fn test_transpose() {
+    let a: [[i32; 2]; 3] = arr_transpose![0; 2, 3; rows [[1, 2, 3], [4, 5, 6]]];
+    assert_eq!(a, [[1, 4], [2, 5], [3, 6]]);
+    let rows = [[1, 2, 3], [4, 5, 6]];
+    for (r, row) in rows.iter().enumerate() {
+        for (c, value) in row.iter().enumerate() {
+            assert_eq!(a[c][r], *value);
+        }
+    }
+    assert_eq!(
+        arr_transpose![0; 3, 2; rows [[1, 2], [3, 4], [5, 6],]],
+        [[1, 3, 5], [2, 4, 6]]
+    );
+    assert_eq!(arr_transpose![0; 1, 3; rows [[1, 2, 3]]], [[1], [2], [3]]);
+    const ROWS: usize = 2;
+    let b = arr_transpose![""; ROWS, 2; rows [["a", "b"], ["c", "d",]]];
+    assert_eq!(b, [["a", "c"], ["b", "d"]]);
+    let empty: [[i32; 0]; 3] = arr_transpose![0; 0, 3; rows []];
+    assert_eq!(empty, [[], [], []]);
+}
+
+#[test]
+#[should_panic(expected = "array-lit: 1 rows were given, but 2 were expected")]
+fn test_transpose_wrong_rows() {
+    const ROWS: usize = 2;
+    let _ = arr_transpose![0; ROWS, 2; rows [[1, 2]]];
+}
+
+#[test]
+#[should_panic(expected = "array-lit: a row has 3 values, but 2 were expected")]
+fn test_transpose_wrong_row_len() {
+    const COLS: usize = 2;
+    let _ = arr_transpose![0; 2, COLS; rows [[1, 2], [3, 4, 5]]];
+}
+
+#[test]
+fn test_default_fill() {
+    #[derive(PartialEq, Debug, Default)]
+    struct X(i32); // does NOT implement Copy
+
+    let a: [X; 3] = arr![default; 3];
+    assert_eq!(a, [X(0), X(0), X(0)]);
+    let a: [X; 3] = arr![default; 3; { 1: X(5) }];
+    assert_eq!(a, [X(0), X(5), X(0)]);
+    assert_eq!(arr![default; 4; { 1..3: 7u8 }], [0, 7, 7, 0]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_default_fill_vec() {
+    let len = 3;
+    let v: Vec<String> = vec![default; len; { 0: "a".to_string() }];
+    assert_eq!(v, ["a", "", ""]);
+    assert_eq!(vec![default; 2; { ^0: 1 }], [0, 1]);
+}
+
+#[test]
+fn test_try_arr() {
+    assert_eq!(try_arr![3; 2], Ok([3, 3]));
+    assert_eq!(try_arr![1, 2], Ok([1, 2]));
+    assert_eq!(try_arr![0; 4; { 1: 1, [2]: [2, 3] }], Ok([0, 1, 2, 3]));
+
+    let (i, j) = (4, 6);
+    assert_eq!(
+        try_arr![0; 4; { i: 1, j: 2 }],
+        Err(IndexError { index: 4, len: 4 })
+    );
+    assert_eq!(
+        try_arr![0; 4; { [3]: [1, 2] }],
+        Err(IndexError { index: 4, len: 4 })
+    );
+    let src = [1, 2, 3];
+    assert_eq!(
+        try_arr![0; 4; { [2]: src }],
+        Err(IndexError { index: 4, len: 4 })
+    );
+    assert_eq!(
+        try_arr![default; 2; { 1..3: 1u8 }],
+        Err(IndexError { index: 2, len: 2 })
+    );
+
+    let i = 9;
+    assert_eq!(try_arr![fn |k| k; 3; { 1: 7 }], Ok([0, 7, 2]));
+    assert_eq!(
+        try_arr![fn |k| k; 4; { i: 1 }],
+        Err(IndexError { index: 9, len: 4 })
+    );
+    assert_eq!(try_arr![0; 4; { ^1: 1 }], Ok([0, 0, 1, 0]));
+    assert_eq!(
+        try_arr![0; 4; { ^i: 1 }],
+        Err(IndexError { index: 9, len: 4 })
+    );
+    assert_eq!(
+        try_arr![_; 3; { ..: fn |k| k, i: 1 }],
+        Err(IndexError { index: 9, len: 3 })
+    );
+    assert_eq!(try_arr![0; _; { 2: 1 }], Ok([0, 0, 1]));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_try_vec() {
+    assert_eq!(try_vec![3; 2], Ok(std::vec![3, 3]));
+    assert_eq!(
+        try_vec![String::new(); 2; { 0: "a".to_string() }],
+        Ok(std::vec!["a".to_string(), String::new()])
+    );
+
+    let len = 3;
+    assert_eq!(
+        try_vec![0; len; { 2..: 1, 3: 1 }],
+        Err(IndexError { index: 3, len: 3 })
+    );
+    let i = 9;
+    assert_eq!(
+        try_vec![fn |k| k; len; { i: 1 }],
+        Err(IndexError { index: 9, len: 3 })
+    );
+    assert_eq!(
+        try_vec![0; len; { ^i: 1 }],
+        Err(IndexError { index: 9, len: 3 })
+    );
+    assert_eq!(try_vec![0; auto; { 2: 1 }], Ok(std::vec![0, 0, 1]));
+    assert_eq!(
+        IndexError { index: 3, len: 3 }.to_string(),
+        "index 3 is out of bounds for length 3"
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_try_vec_fallible_elements() {
+    use std::num::ParseIntError;
+
+    let input = ["1", "2", "3"];
+    let v: Result<Vec<i32>, ParseIntError> = try_vec![|i| input[i].parse()?; 3];
+    assert_eq!(v, Ok(std::vec![1, 2, 3]));
+
+    // the elements after the first error aren't computed
+    let input = ["1", "x", "y"];
+    let mut calls = 0;
+    let v: Result<Vec<i32>, ParseIntError> = try_vec![|i| {
+        calls += 1;
+        input[i].parse()?
+    }; 3];
+    assert_eq!(v.unwrap_err().to_string(), "invalid digit found in string");
+    assert_eq!(calls, 2);
+
+    // the error is converted with `From`
+    let v: Result<Vec<u8>, Box<dyn std::error::Error>> = try_vec![|_| "300".parse()?; 1];
+    assert!(v.is_err());
+    assert_eq!(try_vec![|i| i * 2; 0], Ok::<_, ()>(std::vec![]));
+}
+
+#[test]
+fn test_const_arr() {
+    const HEADER: [i32; 2] = [1, 2];
+    const A: [i32; 8] = const_arr![0; 8; { [0]: [1; 8] }];
+    const B: [i32; 8] = const_arr![0; 8; {
+        [0]: HEADER,
+        [2]: [3, 4],
+        4..6: 5,
+        ^0: 6,
+    }];
+    static C: [i32; 3] = const_arr![7; 3];
+
+    assert_eq!(A, [1; 8]);
+    assert_eq!(B, [1, 2, 3, 4, 5, 5, 0, 6]);
+    assert_eq!(C, [7; 3]);
+    assert_eq!(const_arr![0; 4; { [1]: &HEADER[..] }], [0, 1, 2, 0]);
+}
+
+#[test]
+fn test_const_arr_fill() {
+    const LEN: usize = 3;
+    const A: [u8; 8] = const_arr![0; 8; { [1]: [7; LEN], [5]: [9; 0], [6]: [2; 2] }];
+    assert_eq!(A, [0, 7, 7, 7, 0, 0, 2, 2]);
+
+    const fn make<const N: usize>(len: usize) -> [i32; N] {
+        const_arr![0; N; { [(N - len)]: [1; len] }]
+    }
+    const B: [i32; 5] = make(2);
+    assert_eq!(B, [0, 0, 0, 1, 1]);
+    assert_eq!(crate::__fill_const([0; 4], 1, 2, 5), [0, 5, 5, 0]);
+}
+
+#[test]
+fn test_const_arr_advanced_forms() {
+    const HEADER: [u8; 4] = [1, 2, 3, 4];
+    const BODY: core::ops::Range<usize> = 4..6;
+    const A: [u8; 8] = const_arr![0; 8; {
+        [0]: HEADER[1..3] of 2,
+        in [2, 5]: 7,
+        range BODY: += 1,
+        ^0: 9,
+    }];
+    assert_eq!(A, [2, 3, 7, 0, 1, 8, 0, 9]);
+
+    const B: [u8; 8] = const_arr![0; 8; as a { [0]: [1, 2] * 2, 4: a[1] + 3, 5 | 6: 6 }];
+    assert_eq!(B, [1, 2, 1, 2, 5, 6, 6, 0]);
+
+    const C: [u8; 8] = const_arr![0; 8; { [0]: [1, 2, 3, 4], mirror, rev 0..2: 5 }];
+    assert_eq!(C, [5, 5, 3, 4, 4, 3, 2, 1]);
+
+    const D: [u8; 6] = const_arr![0; 6; { 0..6;2: 1, 1..=2: *= 4, 2..4: from 0, (1 + 4): 8 }];
+    assert_eq!(D, [1, 0, 1, 0, 1, 8]);
+
+    const E: [(u8, u8); 3] = const_arr![(0, 0); 3; { 1 .0: 5, 0..3: |a| a[1] }: (u8, u8)];
+    assert_eq!(E, [(5, 0); 3]);
+
+    static F: [u8; 4] = const_arr![0; 4; { #[cfg(all())] 1: 1, clamp 10: 2, [2..]: [3; 5] }];
+    assert_eq!(F, [0, 1, 3, 3]);
+
+    const fn tail<const N: usize>(src: [u8; 4], from: usize) -> [u8; N] {
+        const_arr![0; N; { [0]: src[from..] of N, 0: += 10 }]
+    }
+    const G: [u8; 2] = tail(HEADER, 2);
+    assert_eq!(G, [13, 4]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_non_copy_block_values_vec() {
+    #[derive(PartialEq, Debug, Clone)]
+    struct X(i32); // does NOT implement Copy
+
+    assert_eq!(
+        vec![X(0); 4; { [1]: [X(1), X(2)], [3]: [X(3); 1] }],
+        std::vec![X(0), X(1), X(2), X(3)]
+    );
+}
+
+#[test]
+fn test_repeat_pattern() {
+    assert_eq!(arr![0; 8; { repeat: [1, 2] }], [1, 2, 1, 2, 1, 2, 1, 2]);
+    assert_eq!(arr![0; 5; { repeat: [1, 2, 3] }], [1, 2, 3, 1, 2]);
+    assert_eq!(arr![0; 2; { repeat: [1, 2, 3] }], [1, 2]);
+    assert_eq!(arr![9; 3; { repeat: [] }], [9; 3]);
+    assert_eq!(arr![0; 4; { repeat: [1, 2], 3: 5 }], [1, 2, 1, 5]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_repeat_pattern_vec() {
+    let pattern = std::vec![1, 2];
+    assert_eq!(vec![0; 3; { repeat: pattern }], std::vec![1, 2, 1]);
+}
+
+#[test]
+fn test_repeat_pattern_fn() {
+    // the length of `dst` isn't a multiple of the pattern length
+    let mut a = [0; 7];
+    repeat_pattern(&mut a, &[1, 2, 3]);
+    assert_eq!(a, [1, 2, 3, 1, 2, 3, 1]);
+    let mut a = [0; 2];
+    repeat_pattern(&mut a, &[1, 2, 3]);
+    assert_eq!(a, [1, 2]);
+    repeat_pattern(&mut a[1..], &[4]);
+    assert_eq!(a, [1, 4]);
+
+    // an empty pattern leaves `dst` unchanged
+    let mut a = [5; 3];
+    repeat_pattern(&mut a, &[]);
+    assert_eq!(a, [5; 3]);
+    repeat_pattern(&mut [0u8; 0], &[1]);
+
+    const A: [u8; 5] = const_arr![0; 5; { repeat: [1, 2] }];
+    assert_eq!(A, [1, 2, 1, 2, 1]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_repeat_pattern_clone() {
+    let mut a = [String::new(), String::new(), String::new()];
+    repeat_pattern(&mut a, &["a".to_string(), "b".to_string()]);
+    assert_eq!(a, ["a", "b", "a"]);
+    let a = arr![fn |_| String::new(); 3; { repeat: ["x".to_string()] }];
+    assert_eq!(a, ["x", "x", "x"]);
+}
+
+#[test]
+fn test_shared_value_indices() {
+    assert_eq!(arr![0; 8; { 1 | 3 | 6: 9 }], [0, 9, 0, 9, 0, 0, 9, 0]);
+    assert_eq!(arr![0; 4; { 0 | 3: 1, 1: 2 }], [1, 2, 0, 1]);
+
+    let mut calls = 0;
+    let mut next = || {
+        calls += 1;
+        calls
+    };
+    assert_eq!(arr![0; 4; { 0 | 2 | 3: next() }], [1, 0, 1, 1]);
+    assert_eq!(calls, 1);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_shared_value_indices_vec() {
+    assert_eq!(
+        vec![String::new(); 3; { 0 | 2: "a".to_string() }],
+        ["a", "", "a"]
+    );
+}
+
+#[test]
+fn test_elements_from_index_with_overrides() {
+    assert_eq!(arr![fn |i| i * 10; 5; { 0: 1, 3..: 0 }], [1, 10, 20, 0, 0]);
+
+    #[derive(PartialEq, Debug)]
+    struct X(usize); // does NOT implement Copy
+
+    assert_eq!(arr![fn X; 3; { 1: X(7) }], [X(0), X(7), X(2)]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_elements_from_index_with_overrides_vec() {
+    let n = 3;
+    assert_eq!(
+        vec![fn |i| i.to_string(); n; { ^0: "last".to_string() }],
+        ["0", "1", "last"]
+    );
+}
+
+#[test]
+fn test_inferred_length() {
+    assert_eq!(arr![0; _; { 0: 1, 4: 2 }], [1, 0, 0, 0, 2]);
+    assert_eq!(arr![0; _; { 2..4: 1, 0: 2 }], [2, 0, 1, 1]);
+    assert_eq!(arr![0; _; { 1..=2: 1 }], [0, 1, 1]);
+    assert_eq!(arr![0; _; { [1]: [1, 2], [0]: [3; 1] }], [3, 1, 2]);
+    let empty: [i32; 0] = arr![0; _; {}];
+    assert_eq!(empty, []);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_spread_vec() {
+    let other = [3, 4];
+    let tail = std::vec![10, 11];
+    assert_eq!(vec![1, 2, ..other, 9], std::vec![1, 2, 3, 4, 9]);
+    assert_eq!(vec![..other], std::vec![3, 4]);
+    assert_eq!(vec![..other, ..tail,], std::vec![3, 4, 10, 11]);
+    assert_eq!(
+        vec![0, 1, 2, 3, 4, 5, 6, 7, 8, ..other, -1, ..tail],
+        std::vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 3, 4, -1, 10, 11]
+    );
+    assert_eq!(other, [3, 4]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_arr_uninit() {
+    let a = arr_uninit![3; { 2: String::from("c"), 0: String::from("a"), 1: String::new() }];
+    assert_eq!(a, ["a", "", "c"]);
+    let empty: [String; 0] = arr_uninit![0; {}];
+    assert!(empty.is_empty());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_hash_map() {
+    let a = hash_map! { "a": 1, "b": 2, "a": 3, };
+    assert_eq!(a.len(), 2);
+    assert_eq!(a["a"], 3);
+    assert_eq!(a["b"], 2);
+    let key = 4;
+    let b = hash_map! { (key + 1): 'x' };
+    assert_eq!(b[&5], 'x');
+    let empty: std::collections::HashMap<i32, i32> = hash_map! {};
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_clamped_slices() {
+    assert_eq!(
+        arr![0; 8; { [6..]: [1, 2, 3, 4] }],
+        [0, 0, 0, 0, 0, 0, 1, 2]
+    );
+    assert_eq!(arr![0; 4; { [2..]: [7; 5] }], [0, 0, 7, 7]);
+    assert_eq!(arr![0; 4; { [9..]: [1, 2], [0..]: [] }], [0; 4]);
+    let buf = [1, 2, 3];
+    assert_eq!(arr![0; 4; { [2..]: buf, 0: 9 }], [9, 0, 1, 2]);
+    assert_eq!(arr![0; 4; { [1..]: &buf[..1] }], [0, 1, 0, 0]);
+    const A: [u8; 3] = const_arr![0; 3; { [1..]: [5, 6, 7] }];
+    assert_eq!(A, [0, 5, 6]);
+}
+
+#[test]
+#[cfg(not(any(feature = "sorted-indices", feature = "check-overlap")))]
+fn test_source_order() {
+    assert_eq!(arr![0; 5; { 2: 7, 0..5: 1 }], [1; 5]);
+}
+
+#[test]
+#[cfg(all(feature = "sorted-indices", not(feature = "check-overlap")))]
+fn test_sorted_order() {
+    assert_eq!(arr![0; 5; { 2: 7, 0..5: 1 }], [1, 1, 7, 1, 1]);
+    assert_eq!(arr![0; 4; { 3: 3, [1]: [1, 2, 9], 1: 5 }], [0, 5, 2, 3]);
+    const A: [i32; 3] = arr![0; 3; { 1: 1, 0..3: 2 }];
+    assert_eq!(A, [2, 1, 2]);
+}
+
+#[test]
+fn test_method_chaining() {
+    let sum: i32 = arr![1; 4; { 3: 5 }].iter().sum();
+    assert_eq!(sum, 8);
+    assert_eq!(arr![fn |i| i; 4].len(), 4);
+    arr![0; 2; { 0: 1 }].iter().for_each(|&x| assert!(x < 2));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_method_chaining_vec() {
+    let sum: i32 = vec![1; 4; { 3: 5 }].iter().sum();
+    assert_eq!(sum, 8);
+    let boxed = vec![0; 3; { 1: 1 }].into_boxed_slice();
+    assert_eq!(*boxed, [0, 1, 0]);
+    assert_eq!(vec![1, ..[2, 3]].len(), 3);
+}
+
+#[test]
+fn test_copy_within() {
+    assert_eq!(
+        arr![0; 8; { [0]: [1, 2, 3, 4], 4..8: from 0 }],
+        [1, 2, 3, 4, 1, 2, 3, 4]
+    );
+    // overlapping source and destination
+    assert_eq!(
+        arr![0; 5; { [0]: [1, 2, 3], 1..4: from 0 }],
+        [1, 1, 2, 3, 0]
+    );
+    assert_eq!(
+        arr![0; 5; { [2]: [1, 2, 3], 1..4: from 2 }],
+        [0, 1, 2, 3, 3]
+    );
+    const A: [u8; 5] = const_arr![0; 5; { [0]: [1, 2, 3], 1..4: from 0 }];
+    assert_eq!(A, [1, 1, 2, 3, 0]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_copy_within_vec() {
+    assert_eq!(vec![0; 4; { 0: 7, 1..4: from 0 }], std::vec![7, 7, 0, 0]);
+}
+
+#[test]
+fn test_collect() {
+    assert_eq!(arr![collect 0..4; 4], [0, 1, 2, 3]);
+    assert_eq!(arr![collect 0..10; 3], [0, 1, 2]);
+    assert_eq!(try_arr![collect [5, 6]; 2], Ok([5, 6]));
+    assert_eq!(try_arr![collect 0..10; 3], Ok([0, 1, 2]));
+    assert_eq!(
+        try_arr![collect 0..2; 3],
+        Err(TooFewItemsError {
+            yielded: 2,
+            expected: 3
+        })
+    );
+}
+
+#[test]
+#[should_panic(expected = "the iterator yielded 2 items, but 3 were expected")]
+fn test_collect_too_few() {
+    let _ = arr![collect 0..2; 3];
+}
+
+#[test]
+fn test_groups() {
+    assert_eq!(arr![0; 6; { base 2 { 0: 1, 2: 2 } }], [0, 0, 1, 0, 2, 0]);
+    assert_eq!(
+        arr![0; 8; { 0: 1, base 4 { 0: 2, 2..: 3 }, 1: 4 }],
+        [1, 4, 0, 0, 2, 0, 3, 3]
+    );
+    assert_eq!(
+        arr![0; 6; { base 1 { 0: 1, base 2 { [0]: [2, 3] } }, 5: 4 }],
+        [0, 1, 0, 2, 3, 4]
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_groups_vec() {
+    assert_eq!(vec![0; 4; { base 2 { 1: 1 } }], std::vec![0, 0, 0, 1]);
+}
+
+#[test]
+fn test_const_generic_len() {
+    fn ones<const N: usize>() -> [i32; N] {
+        arr![0; N; { 0: 1, ^0: 1 }]
+    }
+    fn ramp<const N: usize>() -> [usize; N] {
+        arr![fn |i| i; N; { [0]: [9; 1], 1..: 2 }]
+    }
+    fn first<const N: usize>() -> Result<[u8; N], IndexError> {
+        try_arr![0; N; { 1: 1 }]
+    }
+    fn filled<const N: usize>() -> [i32; N] {
+        arr![default; N; { 0..N: 5 }]
+    }
+    assert_eq!(ones::<3>(), [1, 0, 1]);
+    assert_eq!(ramp::<3>(), [9, 2, 2]);
+    assert_eq!(first::<2>(), Ok([0, 1]));
+    assert_eq!(first::<1>(), Err(IndexError { index: 1, len: 1 }));
+    assert_eq!(filled::<2>(), [5, 5]);
+    assert_eq!(arr![collect 0..; 2], [0, 1]);
+}
+
+#[test]
+fn test_const_generic_len_const() {
+    const fn header<const N: usize>() -> [u8; N] {
+        const_arr![0; N; { 0: 1, [1]: [2, 3] }]
+    }
+    fn pair<const N: usize>() -> [u8; N] {
+        arr_uninit![N; { 0: 1, 1: 2 }]
+    }
+    const H: [u8; 4] = header::<4>();
+    assert_eq!(H, [1, 2, 3, 0]);
+    assert_eq!(pair::<2>(), [1, 2]);
+}
+
+#[test]
+#[should_panic(expected = "array-lit: every index must be assigned exactly once")]
+fn test_const_generic_len_uninit_missing() {
+    fn pair<const N: usize>() -> [u8; N] {
+        arr_uninit![N; { 0: 1, 1: 2 }]
+    }
+    let _ = pair::<3>();
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "alloc"))]
+fn test_btree_map() {
+    let a = btree_map! { 3: "c", 1: "a", 2: "x", 2: "b" };
+    assert_eq!(a.len(), 3);
+    assert!(a.iter().map(|(&k, _)| k).eq([1, 2, 3]));
+    assert!(a.values().eq(&["a", "b", "c"]));
+    let empty: crate::alloc::collections::BTreeMap<u8, u8> = btree_map! {};
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_each() {
+    #[derive(PartialEq, Debug)]
+    struct X(i32); // does NOT implement Copy
+
+    assert_eq!(arr![each X(0); 3], [X(0), X(0), X(0)]);
+    assert_eq!(arr![each X(0); 3; { 1: X(1) }], [X(0), X(1), X(0)]);
+    let mut calls = 0;
+    let _ = arr![each { calls += 1; calls }; 4];
+    assert_eq!(calls, 4);
+    assert_eq!(
+        try_arr![each X(0); 2; { 2: X(2) }],
+        Err(IndexError { index: 2, len: 2 })
+    );
+
+    let mut n = 0;
+    let a = try_arr![each { n += 1; n }; 3; { 0: 5 }];
+    assert_eq!((a, n), (Ok([5, 2, 3]), 3));
+    let i = 9;
+    let b = try_arr![each { n += 1; n }; 3; { i: 5 }];
+    assert_eq!((b, n), (Err(IndexError { index: 9, len: 3 }), 6));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_each_vec() {
+    let len = 3;
+    let v = vec![each String::new(); len; { 2: "c".to_string() }];
+    assert_eq!(v, ["", "", "c"]);
+    assert_eq!(
+        try_vec![each String::new(); 1; { 0: "a".to_string() }],
+        Ok(std::vec!["a".to_string()])
+    );
+
+    let mut n = 0;
+    let w = try_vec![each { n += 1; n.to_string() }; len; { 1: "x".to_string(), 5: String::new() }];
+    assert_eq!((w, n), (Err(IndexError { index: 5, len: 3 }), 3));
+}
+
+#[test]
+fn test_clone() {
+    struct Counted<'a>(&'a Cell<usize>);
+    impl Clone for Counted<'_> {
+        fn clone(&self) -> Self {
+            self.0.set(self.0.get() + 1);
+            Counted(self.0)
+        }
+    }
+
+    let clones = Cell::new(0);
+    let _ = arr![clone Counted(&clones); 4];
+    assert_eq!(clones.get(), 3);
+    let _: [Counted; 0] = arr![clone Counted(&clones); 0];
+    assert_eq!(clones.get(), 3);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_clone_std() {
+    assert_eq!(arr![clone String::from("a"); 3], ["a", "a", "a"]);
+    assert_eq!(
+        arr![clone std::vec![1, 2]; 3; { 1: std::vec![] }],
+        [std::vec![1, 2], std::vec![], std::vec![1, 2]]
+    );
+    assert_eq!(
+        arr![clone String::new(); 2; { 0: "a".into() } then |s| s.push('b')],
+        ["ab", "b"]
+    );
+    assert_eq!(
+        try_arr![clone String::new(); 2; { 2: "c".into() }],
+        Err(IndexError { index: 2, len: 2 })
+    );
+    assert_eq!(
+        arr_strict![clone std::vec![0]; 2; { 1: std::vec![] }],
+        [std::vec![0], std::vec![]]
+    );
+}
+
+#[test]
+fn test_runtime_range_indices() {
+    let range = 1..3;
+    let inclusive = 2..=4;
+    assert_eq!(arr![0; 5; { in range: 1 }], [0, 1, 1, 0, 0]);
+    assert_eq!(arr![0; 5; { in inclusive: 1, 0: 2 }], [2, 0, 1, 1, 1]);
+    assert_eq!(arr![0; 5; { in ((0..5).step_by(2)): 1 }], [1, 0, 1, 0, 1]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_runtime_range_indices_vec() {
+    let indices = std::vec![0, 3];
+    assert_eq!(vec![0; 4; { in indices: 7 }], [7, 0, 0, 7]);
+}
+
+#[test]
+fn test_then() {
+    assert_eq!(
+        arr![0; 5; { 3: 1 } then |x| *x += 100],
+        [100, 100, 100, 101, 100]
+    );
+    const LEN: usize = 3;
+    assert_eq!(arr![fn |i| i; 3; { 0: 5 } then |x| *x *= 2], [10, 2, 4]);
+    assert_eq!(arr![1; LEN; {} then |x| *x = -*x], [-1, -1, -1]);
+    let a: [u8; 2] = arr![default; 2; { 1: 4 } then |x: &mut u8| *x += 1];
+    assert_eq!(a, [1, 5]);
+
+    let i = 9;
+    assert_eq!(
+        try_arr![0usize; 4; { 1: 1 } then |x| *x += 1],
+        Ok([1, 2, 1, 1])
+    );
+    assert_eq!(
+        try_arr![0usize; 4; { i: 1 } then |x| *x += 1],
+        Err(IndexError { index: 9, len: 4 })
+    );
+    assert_eq!(
+        try_arr![fn |k| k; 3; { i: 1 } then |x| *x *= 2],
+        Err(IndexError { index: 9, len: 3 })
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_then_vec() {
+    assert_eq!(
+        vec![each String::new(); 2; { 0: "a".into() } then |s| s.push('b')],
+        ["ab", "b"]
+    );
+
+    let i = 9;
+    assert_eq!(
+        try_vec![each String::new(); 2; { 0: "a".into() } then |s| s.push('b')],
+        Ok(std::vec!["ab".to_string(), "b".to_string()])
+    );
+    assert_eq!(
+        try_vec![0; 2; { i: 1 } then |x| *x += 1],
+        Err(IndexError { index: 9, len: 2 })
+    );
+}
+
+#[test]
+fn test_arr_concat() {
+    const LOW: [u8; 2] = [0, 1];
+    const HIGH: [u8; 3] = [7, 8, 9];
+    const TABLE: [u8; 9] = arr_concat![LOW, [2, 3], [4; 2], HIGH];
+    assert_eq!(TABLE, [0, 1, 2, 3, 4, 4, 7, 8, 9]);
+
+    static EMPTY: [u8; 0] = arr_concat![[], []];
+    assert_eq!(EMPTY, []);
+
+    let (a, b) = (1, 2);
+    assert_eq!(arr_concat![[a, b], [b, a,], LOW], [1, 2, 2, 1, 0, 1]);
+    assert_eq!(arr_concat![[1, 2]], [1, 2]);
+}
+
+#[test]
+fn test_empty_entries() {
+    assert_eq!(arr![0; 4; { , 0: 1 }], [1, 0, 0, 0]);
+    assert_eq!(arr![0; 4; { 0: 1, }], [1, 0, 0, 0]);
+    assert_eq!(arr![0; 4; { 0: 1,, 2: 3 }], [1, 0, 3, 0]);
+    assert_eq!(arr![0; 4; { ,, [1]: [2, 3],,, 0..1: 1,, }], [1, 2, 3, 0]);
+    assert_eq!(arr![0; 2; { , }], [0, 0]);
+    assert_eq!(arr![0; 2; { ,, }], [0, 0]);
+    const A: [i32; 3] = const_arr![0; 3; { , [0]: [1, 2],, 2: 3 }];
+    assert_eq!(A, [1, 2, 3]);
+}
+
+#[test]
+fn test_enum_indices() {
+    #[derive(Clone, Copy)]
+    #[repr(usize)]
+    enum Color {
+        Red,
+        Green,
+        Blue,
+    }
+    const N: usize = 3;
+    let a = arr![0; N; { Color::Red as usize: 1, Color::Blue as usize: 3 }];
+    assert_eq!(a, [1, 0, 3]);
+    let b =
+        arr![0; 3; { (Color::Green as usize): 2, Color::Red as usize | Color::Blue as usize: 5 }];
+    assert_eq!(b, [5, 2, 5]);
+    const C: [u8; 3] = const_arr![0; 3; { Color::Green as usize: 2, Color::Blue as usize..N: 4 }];
+    assert_eq!(C, [0, 2, 4]);
+    let c = Color::Blue;
+    assert_eq!(try_arr![0; 3; { c as usize: 1 }], Ok([0, 0, 1]));
+}
+
+#[test]
+fn test_read_previous_elements() {
+    let a = arr![1; 6; { 2: |a| a[0] + a[1], 3: |a| a[1] + a[2], 4: |a| a[2] + a[3], 5: |a| a[3] + a[4] }];
+    assert_eq!(a, [1, 1, 2, 3, 5, 8]);
+    let i = 3;
+    let b = arr![0; 4; { [0]: [5, 6], i: |prev| prev[0] * prev[1], 2: |a| a.len() }];
+    assert_eq!(b, [5, 6, 4, 30]);
+    const C: [u8; 4] = const_arr![1; 4; { 1: 2, 3: |a| a[0] + a[1] + a[2] }];
+    assert_eq!(C, [1, 2, 1, 4]);
+    assert_eq!(try_arr![0; 3; { 0: 7, 2: |a| a[0] }], Ok([7, 0, 7]));
+    #[cfg(feature = "std")]
+    assert_eq!(vec![2; 3; { 1: |v| v.iter().sum() }], [2, 6, 2]);
+    const D: [u8; 4] = const_arr![1; 4; { 0: 3, 1..4: |a| a[0] * 2 }];
+    assert_eq!(D, [3, 6, 6, 6]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_read_previous_elements_clone() {
+    let a = arr![each String::from("x"); 3; { 0..3: |a| a[1].clone() + "y" }];
+    assert_eq!(a, ["xy", "xy", "xy"]);
+    let b = vec![String::new(); 4; { 0: "a".into(), 1 | 3: |v| v[0].clone() }];
+    assert_eq!(b, ["a", "a", "", "a"]);
+
+    // a single index moves the value, which doesn't need to be `Clone`
+    #[derive(Debug, PartialEq)]
+    struct Len(usize);
+    let c = arr![each Len(0); 2; { 1: |a| Len(a.len()) }];
+    assert_eq!(c, [Len(0), Len(2)]);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "array-lit: index 7 out of bounds for len 4")]
+fn test_index_out_of_bounds_message() {
+    let i = 7;
+    let _ = arr![0; 4; { i: 1 }];
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "array-lit: index 4 out of bounds for len 4")]
+fn test_range_out_of_bounds_message() {
+    let end = 6;
+    let _ = arr![0; 4; { 2..end: 1 }];
+}
+
+#[test]
+#[cfg(all(feature = "std", debug_assertions))]
+#[should_panic(expected = "array-lit: index 5 out of bounds for len 3")]
+fn test_vec_out_of_bounds_message() {
+    let _ = vec![0; 3; { in [0, 5]: 1 }];
+}
+
+#[test]
+fn test_repeated_block() {
+    assert_eq!(arr![0; 6; { [0]: [1, 2] * 3 }], [1, 2, 1, 2, 1, 2]);
+    assert_eq!(
+        arr![0; 7; { [1]: [1, 2, 3] * 2, 0: 9 }],
+        [9, 1, 2, 3, 1, 2, 3]
+    );
+    assert_eq!(arr![7; 3; { [0]: [1, 2] * 0 }], [7; 3]);
+    let n = 2;
+    assert_eq!(arr![0; 5; { [1]: [4, 5] * n }], [0, 4, 5, 4, 5]);
+    const A: [u8; 4] = const_arr![0; 4; { [0]: [1] * 2, [2]: [3, 4] * 1 }];
+    assert_eq!(A, [1, 1, 3, 4]);
+    assert_eq!(
+        try_arr![0; 4; { [2]: [1, 2] * 2 }],
+        Err(IndexError { index: 4, len: 4 })
+    );
+    #[cfg(feature = "std")]
+    assert_eq!(vec![0; 4; { [0]: [1, 2] * 2 }], [1, 2, 1, 2]);
+}
+
+#[test]
+#[should_panic]
+fn test_repeated_block_out_of_bounds() {
+    let _ = arr![0; 5; { [0]: [1, 2] * 3 }];
+}
+
+#[test]
+fn test_block_at_several_starts() {
+    assert_eq!(arr![0; 8; { [0, 4]: [1, 2] }], [1, 2, 0, 0, 1, 2, 0, 0]);
+    assert_eq!(
+        arr![0; 9; { [0, 3, 6,]: [7; 2] }],
+        [7, 7, 0, 7, 7, 0, 7, 7, 0]
+    );
+    let n = 5;
+    assert_eq!(
+        arr![0; 8; { [1, n]: [1, 2] * 1, 0: 3 }],
+        [3, 1, 2, 0, 0, 1, 2, 0]
+    );
+    // overlapping copies are written in order, so the last one wins
+    assert_eq!(arr![0; 4; { [0, 1]: [1, 2] }], [1, 1, 2, 0]);
+    assert_eq!(arr![0; 4; { [1, 0]: [1, 2] }], [1, 2, 2, 0]);
+
+    const A: [u8; 6] = const_arr![0; 6; { [0, 3]: [1, 2] }];
+    assert_eq!(A, [1, 2, 0, 1, 2, 0]);
+    assert_eq!(
+        arr![0; 6; as a { [0, 3]: [1, 2], 5: a[0] + 8 }],
+        [1, 2, 0, 1, 2, 9]
+    );
+    assert_eq!(
+        try_arr![0; 4; { [0, 3]: [1, 2] }],
+        Err(IndexError { index: 4, len: 4 })
+    );
+    #[cfg(feature = "std")]
+    assert_eq!(vec![0; auto; { [0, 3]: [1, 2] }], [1, 2, 0, 1, 2]);
+}
+
+#[test]
+fn test_from_pairs() {
+    let pairs = [(1, 5), (3, 7)];
+    assert_eq!(arr![0; 5; { from_pairs pairs }], [0, 5, 0, 7, 0]);
+    assert_eq!(
+        arr![0; 6; { 0: 1, from_pairs (0..3).map(|i| (i * 2, 9)), ^0: 4 }],
+        [9, 0, 9, 0, 9, 4]
+    );
+    assert_eq!(
+        try_arr![0; 2; { from_pairs [(0, 1), (2, 3)] }],
+        Err(IndexError { index: 2, len: 2 })
+    );
+    #[cfg(feature = "std")]
+    {
+        let pairs: Vec<(usize, i32)> = std::vec![(0, -1), (2, 8)];
+        assert_eq!(vec![0; 3; { from_pairs pairs }], [-1, 0, 8]);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_from_pairs_out_of_bounds() {
+    let _ = arr![0; 2; { from_pairs [(2, 1)] }];
+}
+
+#[test]
+fn test_index_expressions() {
+    const BASE: usize = 2;
+    const C: [i32; 8] = const_arr![0; 8; { (BASE * 3 - 1): 1, (BASE + 1) | (7 % 4 * 2): 2 }];
+    assert_eq!(C, [0, 0, 0, 2, 0, 1, 2, 0]);
+    let (base, offset) = (3, 2);
+    let one = &1;
+    let small = 3u8;
+    assert_eq!(arr![0; 8; { (base + offset * 2 - 1): 1 }][6], 1);
+    assert_eq!(arr![0; 8; { (base * offset / 3 + 8 / 2 % 3): 1 }][3], 1);
+    assert_eq!(arr![0; 9; { ((base + 1) * 2): 1 }][8], 1);
+    assert_eq!(arr![0; 4; { (*one + *one * 2): 1 }][3], 1);
+    assert_eq!(arr![0; 4; { (base - 2 - 1): 1 }][0], 1);
+    assert_eq!(arr![0; 6; { (small as usize + [1, 2][1]): 1 }][5], 1);
+    assert_eq!(arr![0; 4; { (base): += 2 }][3], 2);
+    assert_eq!(arr![0; 4; { (base - 1): fn |i| i }][2], 2);
+    #[cfg(feature = "std")]
+    assert_eq!(vec![0; auto; { (base + 1): 1 }], [0, 0, 0, 0, 1]);
+}
+
+#[test]
+#[cfg(feature = "checked-indices")]
+#[should_panic(expected = "array-lit: index overflow")]
+fn test_checked_index_add() {
+    let big = usize::MAX - 1;
+    let _ = arr![0; 4; { (big + 2): 1 }];
+}
+
+#[test]
+#[cfg(feature = "checked-indices")]
+#[should_panic(expected = "array-lit: index overflow")]
+fn test_checked_index_mul() {
+    let big = usize::MAX / 2 + 1;
+    let _ = arr![0; 4; { (1 + big * 2): 1 }];
+}
+
+#[test]
+#[cfg(feature = "checked-indices")]
+#[should_panic(expected = "array-lit: index overflow")]
+fn test_checked_index_sub() {
+    let one = 1;
+    let _ = arr![0; 4; { (one - 2 + 3): 1 }];
+}
+
+#[test]
+#[cfg(feature = "checked-indices")]
+#[should_panic(expected = "array-lit: index overflow")]
+fn test_checked_index_alternatives() {
+    let big = usize::MAX;
+    let _ = arr![0; 4; { 0 | (big * big): += 1 }];
+}
+
+#[test]
+fn test_prefix_then_fill() {
+    assert_eq!(arr![1, 2, 3; 8; 0], [1, 2, 3, 0, 0, 0, 0, 0]);
+    assert_eq!(arr![1, 2, 3,; 3; 0], [1, 2, 3]);
+    assert_eq!(arr![7; 2; 0], [7, 0]);
+    assert_eq!(arr![b'a', b'b'; 4; b' '], *b"ab  ");
+    const LEN: usize = 4;
+    let a: [i32; LEN] = arr![-1, 1 + 1; LEN; 5];
+    assert_eq!(a, [-1, 2, 5, 5]);
+    const B: [u8; 3] = arr![9; 3; 1];
+    assert_eq!(B, [9, 1, 1]);
+}
+
+#[test]
+#[should_panic(expected = "array-lit: 3 values were given, but the length is 2")]
+fn test_prefix_too_long() {
+    const LEN: usize = 2;
+    let _ = arr![1, 2, 3; LEN; 0];
+}
+
+#[test]
+fn test_expression_values() {
+    fn compute() -> u8 {
+        7
+    }
+    let flag = true;
+    let a = arr![0u16; 4; { 0: (1u8 as u16), 1: compute().into() }];
+    assert_eq!(a, [1, 7, 0, 0]);
+    let b: [u16; 4] = arr![0; 4; { 0: 5u8 as _, 1: 5u8 as u16, 2..4: compute() as u16 }];
+    assert_eq!(b, [5, 5, 7, 7]);
+    let c = arr![0; 4; {
+        0: if flag { 1 } else { 2 },
+        1 | 2: if !flag { 1 } else { 2 },
+        3: match flag { true => 3, false => 4 },
+    }];
+    assert_eq!(c, [1, 2, 2, 3]);
+    let d = arr![0u64; 4; {
+        0: u64::from(compute()),
+        [1]: [compute().into(), 2u8 as u64],
+        3: "5".parse::<u64>().unwrap() * 2,
+    }];
+    assert_eq!(d, [7, 7, 2, 10]);
+    let e = arr![0u16; 4; as a {
+        0: 3u8 as u16,
+        1: a[0] as u8 as u16 + 1,
+        2: if a[1] > 3 { 9 } else { 0 },
+    }];
+    assert_eq!(e, [3, 4, 9, 0]);
+    let f = arr![0u16; 3; { 0: += 1u8 as u16, 1: += u16::from(compute()), 2: |a| a[0] + 1 }];
+    assert_eq!(f, [1, 7, 2]);
+    // more than eight literal indices are applied in batches
+    let g = arr![0u16; 10; {
+        0: 1u8 as u16, 1: 2, 2: 3, 3: 4, 4: 5, 5: 6, 6: 7, 7: 8,
+        8: compute().into(), 9: if flag { 1 } else { 0 },
+    }];
+    assert_eq!(g, [1, 2, 3, 4, 5, 6, 7, 8, 7, 1]);
+    const H: [u16; 3] = const_arr![0; 3; { 0: 1u8 as u16, 1: if true { 2 } else { 3 } }];
+    assert_eq!(H, [1, 2, 0]);
+    #[cfg(feature = "std")]
+    assert_eq!(
+        vec![0u16; 3; { 0: compute().into(), 2: 3u8 as _ }],
+        [7, 0, 3]
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_remove() {
+    assert_eq!(vec![0; 8; { remove 3 }].len(), 7);
+    // the indices refer to the positions before the first removal
+    assert_eq!(
+        vec![0; 6; { [0]: [0, 1, 2, 3, 4, 5], remove 1, remove 4, remove 1 }],
+        [0, 2, 3, 5]
+    );
+    assert_eq!(
+        vec![0; 6; { [0]: [0, 1, 2, 3, 4, 5], remove 0, remove 5, }],
+        [1, 2, 3, 4]
+    );
+    // removals are applied after the other entries
+    assert_eq!(vec![0; 4; { 3: 1, 0..4: += 1, remove 0 }], [1, 1, 2]);
+    assert_eq!(vec![0; auto; { 3: 1, remove 0 }], [0, 0, 1]);
+    assert_eq!(vec![from [1, 2, 3]; { 0: 4, remove 2 }], [4, 2]);
+    let i = 2;
+    assert_eq!(vec![fn |i| i; 5; { remove i + 1, remove i }], [0, 1, 4]);
+    assert!(vec![0; 1; { remove 0 }].is_empty());
+}
+
+#[test]
+#[cfg(feature = "std")]
+#[should_panic(expected = "array-lit: index 3 out of bounds for len 3")]
+fn test_remove_out_of_bounds() {
+    let _ = vec![0; 3; { remove 3 }];
+}
+
+#[test]
+fn test_element_fields() {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+    const P: Point = Point { x: 0, y: 0 };
+
+    let a = arr![(0u8, 0u8); 4; { 3 .0: 5, 3 .1: 9, (1).1: 2 }];
+    assert_eq!(a, [(0, 0), (0, 2), (0, 0), (5, 9)]);
+    let i = 2;
+    let b = arr![P; 3; { 0.x: 1, (i).y: 2, 1: Point { x: 3, y: 3 }, 1.y: += 1 }];
+    assert_eq!(
+        b,
+        [
+            Point { x: 1, y: 0 },
+            Point { x: 3, y: 4 },
+            Point { x: 0, y: 2 }
+        ]
+    );
+    // nested fields
+    assert_eq!(
+        arr![((0, 0), 0); 2; { 1 .0 .1: 7 }],
+        [((0, 0), 0), ((0, 7), 0)]
+    );
+    let c = arr![(0, 0); 3; as a { 0: (1, 2), 1 .1: a[0].1 + 1 }];
+    assert_eq!(c, [(1, 2), (0, 3), (0, 0)]);
+    // an identifier with a field is still an index expression
+    let pos = (1, 0);
+    assert_eq!(arr![0; 3; { pos.0: 1 }], [0, 1, 0]);
+
+    const T: [(u8, u8); 3] = const_arr![(0, 0); 3; { 1 .0: 5, 2 .1: 9, (0).1: 1 }];
+    assert_eq!(T, [(0, 1), (5, 0), (0, 9)]);
+    #[cfg(feature = "std")]
+    {
+        assert_eq!(vec![(0, 0); auto; { 2 .1: 1 }], [(0, 0), (0, 0), (0, 1)]);
+        assert_eq!(
+            vec![P; 2; { 1.x: 3, (i - 2).y: 4 }],
+            [Point { x: 0, y: 4 }, Point { x: 3, y: 0 }]
+        );
+    }
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn test_element_fields_out_of_bounds() {
+    let i = 5;
+    let _ = arr![(0, 0); 3; { (i).1: 1 }];
+}
+
+#[test]
+fn test_element_type() {
+    // without the type, calling a method on the elements is ambiguous
+    let a = arr![0; 8; { 0: 1 } : i64];
+    assert_eq!(a[0].leading_zeros(), 63);
+    assert_eq!(arr![255; 2; {} : u8][0].count_ones(), 8);
+    const N: usize = 3;
+    assert_eq!(arr![0; N; { ^0: 1 } : u16][2].leading_zeros(), 15);
+    assert_eq!(arr![0; N + 1; { 0..2: 1 } : u32][3].leading_zeros(), 32);
+    let b = arr![0; _; { 2: 1 } : u32];
+    assert_eq!(b.len(), 3);
+    assert_eq!(b[2].leading_zeros(), 31);
+    assert_eq!(arr![fn |i| i as _; 4; { 0: 7 } : u8][3].leading_zeros(), 6);
+    assert_eq!(arr![default; 2; { 1: 1 } : i8][1].leading_zeros(), 7);
+    assert_eq!(arr![each 1 + 1; 2; {} : u64][0].leading_zeros(), 62);
+    assert_eq!(arr![clone 3; 2; {} : i16][0].leading_zeros(), 14);
+    assert_eq!(arr![_; 2; { ..: 5 } : u8][0].leading_zeros(), 5);
+    assert_eq!(arr![0.0; 2; { 0: 4.0 } : f32][0].sqrt(), 2.0);
+
+    let i = 9;
+    let c = try_arr![0; 4; { 1: 1 } : u8].map(|c| c[1].leading_zeros());
+    assert_eq!(c, Ok(7));
+    assert_eq!(
+        try_arr![0; 4; { i: 1 } : u8],
+        Err(IndexError { index: 9, len: 4 })
+    );
+    assert_eq!(
+        try_arr![fn |k| k as _; 2; { i: 1 } : u8],
+        Err(IndexError { index: 9, len: 2 })
+    );
+    assert_eq!(try_arr![0; _; { 1: 1 } : u16], Ok([0, 1]));
+}
+
+#[test]
+fn test_len_expressions() {
+    use core::mem::size_of;
+
+    const fn double(n: usize) -> usize {
+        n * 2
+    }
+    const N: usize = size_of::<u32>();
+
+    assert_eq!(
+        arr![0u8; core::mem::size_of::<u64>(); { 0: 1 }],
+        [1, 0, 0, 0, 0, 0, 0, 0]
+    );
+    assert_eq!(arr![0; size_of::<u16>() * 2], [0; 4]);
+    assert_eq!(arr![0; double(N) - 6; { ^0: 1 } then |x| *x += 1], [1, 2]);
+    assert_eq!(
+        arr![0; N + 1; as a { 0: 1, 1..N + 1: a[0] + 1 }],
+        [1, 2, 2, 2, 2]
+    );
+    assert_eq!(
+        arr![0; size_of::<u16>(); { 1: 1 } : u8][1].leading_zeros(),
+        7
+    );
+    assert_eq!(arr![fn |i| i; double(2); {} : usize], [0, 1, 2, 3]);
+    assert_eq!(arr![default; N - 2; {} : u8], [0, 0]);
+    assert_eq!(arr![each 3; N / 2; {} : u8], [3, 3]);
+    assert_eq!(arr![clone 1; N % 3; {} : u8], [1]);
+    assert_eq!(arr![_; N - 1; { ..: 2 } : u8], [2, 2, 2]);
+    assert_eq!(arr![1, 2; N + size_of::<u8>(); 0], [1, 2, 0, 0, 0]);
+
+    const A: [u8; 8] = const_arr![0; size_of::<u64>(); { 0..N: 1 }];
+    assert_eq!(A, [1, 1, 1, 1, 0, 0, 0, 0]);
+}
+
+#[test]
+fn test_labels() {
+    let plain = arr![0; 8; { [0]: [1, 2, 3, 4], 5: 6, 6..8: 7 }];
+    let labeled = arr![0; 8; { "header" [0]: [1, 2, 3, 4], "id" 5: 6, "tail" 6..8: 7 }];
+    assert_eq!(labeled, plain);
+
+    let i = 2;
+    let a = arr![0; 6; as a {
+        "computed" (i + 1): 3,
+        "from end" ^0: 5,
+        "copy" i: a[3] * 2,
+        "keyword" rev 0..2: 1,
+        "" mirror,
+    }];
+    assert_eq!(a, [1, 1, 6, 6, 1, 1]);
+
+    assert_eq!(arr![0; _; { "a" [0]: [1, 2], "b" 3: 4 }], [1, 2, 0, 4]);
+    const C: [u8; 4] = const_arr![0; 4; { "a" [1]: [1, 2], "b" 0: 3 }];
+    assert_eq!(C, [3, 1, 2, 0]);
+}
+
+#[test]
+fn test_previous_element() {
+    // cumulative sums of the indices
+    let a = arr![0; 6; { 1..6: |prev, i| prev + i }];
+    assert_eq!(a, [0, 1, 3, 6, 10, 15]);
+    let a = arr![0; 6; { 0: 1, 1..: |prev, _| prev * 2 }];
+    assert_eq!(a, [1, 2, 4, 8, 16, 32]);
+
+    // running total of a table
+    let costs = [3, 1, 4, 1, 5];
+    let totals = arr![0; 5; { 0: costs[0], 1..=4: |prev, i| prev + costs[i] }];
+    assert_eq!(totals, [3, 4, 8, 9, 14]);
+
+    // the entries before are visible, and the bounds can be expressions
+    let n = 4;
+    let a = arr![1; 6; as a { 2..n + 1: |prev, i| prev + i, 5: a[4] }];
+    assert_eq!(a, [1, 1, 3, 6, 10, 10]);
+    let a = arr![0u64; 8; { 0..2: 1, 2..8: |prev, i| prev + i as u64 }];
+    assert_eq!(a, [1, 1, 3, 6, 10, 15, 21, 28]);
+    assert_eq!(arr![7; 2; { 0..0: |prev, i| prev + i }], [7, 7]);
+    let n = 2;
+    let a = arr![0; 6; { 0: 1, 1..=n << 1: |prev, _| prev + 1 }];
+    assert_eq!(a, [1, 2, 3, 4, 5, 0]);
+
+    const C: [usize; 5] = const_arr![1; 5; { 1..5: |prev, i| *prev * i }];
+    assert_eq!(C, [1, 1, 2, 6, 24]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_previous_element_vec() {
+    let v = vec![String::from("a"); 3; { 1..: |prev, i| format!("{}{}", prev, i) }];
+    assert_eq!(v, ["a", "a1", "a12"]);
+}
+
+#[test]
+#[should_panic(expected = "index 0 has no previous element")]
+fn test_previous_element_at_zero() {
+    let _ = arr![0; 4; { 0..4: |prev, i| prev + i }];
+}
+
+#[test]
+fn test_mirror() {
+    assert_eq!(
+        arr![0; 8; { [0]: [1, 2, 3, 4], mirror }],
+        [1, 2, 3, 4, 4, 3, 2, 1]
+    );
+    // the middle element of an odd length is kept
+    assert_eq!(
+        arr![0; 7; { [0]: [1, 2, 3, 9], mirror }],
+        [1, 2, 3, 9, 3, 2, 1]
+    );
+    assert_eq!(arr![0; 1; { 0: 1, mirror }], [1]);
+    assert_eq!(arr![0; 0; { mirror }], []);
+    // later entries override the mirrored values
+    assert_eq!(arr![0; 4; { 0: 1, mirror, ^0: 2 }], [1, 0, 0, 2]);
+    assert_eq!(
+        arr![0; 6; as a { 0: 1, 1: a[0] + 1, mirror }],
+        [1, 2, 0, 0, 2, 1]
+    );
+    assert_eq!(
+        arr![0; 7; { 0: 1, base 3 { 0: 5, mirror } }],
+        [1, 0, 0, 5, 0, 0, 5]
+    );
+    const EVEN: [i32; 4] = const_arr![0; 4; { 0: 1, 1: 2, mirror }];
+    assert_eq!(EVEN, [1, 2, 2, 1]);
+    const ODD: [i32; 5] = const_arr![0; 5; { [0]: [1, 2, 3], mirror }];
+    assert_eq!(ODD, [1, 2, 3, 2, 1]);
+    #[cfg(feature = "std")]
+    {
+        let s = arr![clone String::new(); 3; { 0: "a".to_string(), mirror }];
+        assert_eq!(s, ["a", "", "a"]);
+        assert_eq!(vec![0; 4; { 0: 1, 1: 2, mirror }], [1, 2, 2, 1]);
+        assert_eq!(vec![0; auto; { 0: 1, 2: 3, mirror }], [1, 0, 1]);
+    }
+}
+
+#[test]
+fn test_range_entries() {
+    use core::ops::Range;
+
+    const HDR: Range<usize> = 0..3;
+    const TABLE: [u8; 8] = const_arr![0; 8; { range HDR: 1, range (5..7): 2 }];
+    assert_eq!(TABLE, [1, 1, 1, 0, 0, 2, 2, 0]);
+
+    let body = 2..5;
+    let a = arr![0; 6; { range HDR: 1, range (body.clone()): += 10, 5: 9 }];
+    assert_eq!(a, [1, 1, 11, 10, 10, 9]);
+
+    let a = arr![0; 6; { range (body.start + 1..body.end): fn |i| i * 2 }];
+    assert_eq!(a, [0, 0, 0, 6, 8, 0]);
+
+    let a = arr![0; 5; { 0: 3, range (body): |a| a[0] }];
+    assert_eq!(a, [3, 0, 3, 3, 3]);
+
+    let a = arr![0; 4; { range (0..4) where |i| i % 2 == 1: 7 }];
+    assert_eq!(a, [0, 7, 0, 7]);
+    let a = arr![1; 4; as a { range (1..3): a[0] + 1 }];
+    assert_eq!(a, [1, 2, 2, 1]);
+    let empty = 3..3;
+    assert_eq!(arr![0; 4; { range empty: 1 }], [0; 4]);
+
+    // `range` is still a normal identifier in other entries
+    let range = 1;
+    assert_eq!(arr![0; 3; { range: 5, (range + 1): 6 }], [0, 5, 6]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_range_entries_vec() {
+    let tail = 3..5;
+    assert_eq!(vec![0; 5; { range tail: 1 }], std::vec![0, 0, 0, 1, 1]);
+}
+
+#[test]
+#[should_panic]
+fn test_range_entries_out_of_bounds() {
+    let range = 2..5;
+    let _ = arr![0; 4; { range range: 1 }];
+}
+
+#[test]
+fn test_sub_slice_of_len() {
+    let src = [1, 2, 3, 4, 5, 6, 7, 8];
+    assert_eq!(arr![0; 6; { [2]: src[4..8] of 4 }], [0, 0, 5, 6, 7, 8]);
+    let (start, end) = (1, 3);
+    assert_eq!(arr![0; 3; { [0]: src[start..end] of 2, ^0: 9 }], [2, 3, 9]);
+    const LEN: usize = 3;
+    assert_eq!(arr![0; 4; { [1]: src[..LEN] of LEN }], [0, 1, 2, 3]);
+    #[cfg(feature = "std")]
+    {
+        let src = std::vec![7, 8];
+        assert_eq!(vec![0; 3; { [1]: src[..] of 2 }], [0, 7, 8]);
+    }
+}
+
+#[test]
+#[should_panic(expected = "array-lit: the source slice has length 3, but 4 was expected")]
+fn test_sub_slice_of_wrong_len() {
+    let src = [1, 2, 3, 4, 5, 6];
+    let end = 5;
+    let _ = arr![0; 6; { [0]: src[2..end] of 4 }];
+}
+
+#[test]
+fn test_strict() {
+    assert_eq!(arr_strict![0; 4; { 0: 1, 3: 2 }], [1, 0, 0, 2]);
+    assert_eq!(arr_strict![0; 5; { 0 | 2: 1, 4: 2, , }], [1, 0, 1, 0, 2]);
+    let i = 1;
+    // only literal indices are compared
+    assert_eq!(arr_strict![0; 3; { i: 1, 1..3: 2, [0]: [3] }], [3, 2, 2]);
+    assert_eq!(arr_strict![0; _; { 2: 1 }], [0, 0, 1]);
+    assert_eq!(arr_strict![fn |i| i; 3; { 0: 5 }], [5, 1, 2]);
+    assert_eq!(arr_strict![0; 3; { 1: 1 } then |x| *x += 1], [1, 2, 1]);
+    assert_eq!(arr_strict![1, 2], [1, 2]);
+}
+
+#[test]
+fn test_large_block() {
+    // written out in full, since this checks that the recursion limit isn't reached
+    let n = 1;
+    let arr: [usize; 500] = arr![0; 500; {
+        0: n, 1: n + 1, 2: n + 2, 3: n + 3, 4: n + 4, 5: n + 5, 6: n + 6, 7: n + 7, 8: n + 8, 9: n + 9,
+        10: n + 10, 11: n + 11, 12: n + 12, 13: n + 13, 14: n + 14, 15: n + 15, 16: n + 16, 17: n + 17, 18: n + 18, 19: n + 19,
+        20: n + 20, 21: n + 21, 22: n + 22, 23: n + 23, 24: n + 24, 25: n + 25, 26: n + 26, 27: n + 27, 28: n + 28, 29: n + 29,
+        30: n + 30, 31: n + 31, 32: n + 32, 33: n + 33, 34: n + 34, 35: n + 35, 36: n + 36, 37: n + 37, 38: n + 38, 39: n + 39,
+        40: n + 40, 41: n + 41, 42: n + 42, 43: n + 43, 44: n + 44, 45: n + 45, 46: n + 46, 47: n + 47, 48: n + 48, 49: n + 49,
+        50: n + 50, 51: n + 51, 52: n + 52, 53: n + 53, 54: n + 54, 55: n + 55, 56: n + 56, 57: n + 57, 58: n + 58, 59: n + 59,
+        60: n + 60, 61: n + 61, 62: n + 62, 63: n + 63, 64: n + 64, 65: n + 65, 66: n + 66, 67: n + 67, 68: n + 68, 69: n + 69,
+        70: n + 70, 71: n + 71, 72: n + 72, 73: n + 73, 74: n + 74, 75: n + 75, 76: n + 76, 77: n + 77, 78: n + 78, 79: n + 79,
+        80: n + 80, 81: n + 81, 82: n + 82, 83: n + 83, 84: n + 84, 85: n + 85, 86: n + 86, 87: n + 87, 88: n + 88, 89: n + 89,
+        90: n + 90, 91: n + 91, 92: n + 92, 93: n + 93, 94: n + 94, 95: n + 95, 96: n + 96, 97: n + 97, 98: n + 98, 99: n + 99,
+        100: n + 100, 101: n + 101, 102: n + 102, 103: n + 103, 104: n + 104, 105: n + 105, 106: n + 106, 107: n + 107, 108: n + 108, 109: n + 109,
+        110: n + 110, 111: n + 111, 112: n + 112, 113: n + 113, 114: n + 114, 115: n + 115, 116: n + 116, 117: n + 117, 118: n + 118, 119: n + 119,
+        120: n + 120, 121: n + 121, 122: n + 122, 123: n + 123, 124: n + 124, 125: n + 125, 126: n + 126, 127: n + 127, 128: n + 128, 129: n + 129,
+        130: n + 130, 131: n + 131, 132: n + 132, 133: n + 133, 134: n + 134, 135: n + 135, 136: n + 136, 137: n + 137, 138: n + 138, 139: n + 139,
+        140: n + 140, 141: n + 141, 142: n + 142, 143: n + 143, 144: n + 144, 145: n + 145, 146: n + 146, 147: n + 147, 148: n + 148, 149: n + 149,
+        150: n + 150, 151: n + 151, 152: n + 152, 153: n + 153, 154: n + 154, 155: n + 155, 156: n + 156, 157: n + 157, 158: n + 158, 159: n + 159,
+        160: n + 160, 161: n + 161, 162: n + 162, 163: n + 163, 164: n + 164, 165: n + 165, 166: n + 166, 167: n + 167, 168: n + 168, 169: n + 169,
+        170: n + 170, 171: n + 171, 172: n + 172, 173: n + 173, 174: n + 174, 175: n + 175, 176: n + 176, 177: n + 177, 178: n + 178, 179: n + 179,
+        180: n + 180, 181: n + 181, 182: n + 182, 183: n + 183, 184: n + 184, 185: n + 185, 186: n + 186, 187: n + 187, 188: n + 188, 189: n + 189,
+        190: n + 190, 191: n + 191, 192: n + 192, 193: n + 193, 194: n + 194, 195: n + 195, 196: n + 196, 197: n + 197, 198: n + 198, 199: n + 199,
+        200: n + 200, 201: n + 201, 202: n + 202, 203: n + 203, 204: n + 204, 205: n + 205, 206: n + 206, 207: n + 207, 208: n + 208, 209: n + 209,
+        210: n + 210, 211: n + 211, 212: n + 212, 213: n + 213, 214: n + 214, 215: n + 215, 216: n + 216, 217: n + 217, 218: n + 218, 219: n + 219,
+        220: n + 220, 221: n + 221, 222: n + 222, 223: n + 223, 224: n + 224, 225: n + 225, 226: n + 226, 227: n + 227, 228: n + 228, 229: n + 229,
+        230: n + 230, 231: n + 231, 232: n + 232, 233: n + 233, 234: n + 234, 235: n + 235, 236: n + 236, 237: n + 237, 238: n + 238, 239: n + 239,
+        240: n + 240, 241: n + 241, 242: n + 242, 243: n + 243, 244: n + 244, 245: n + 245, 246: n + 246, 247: n + 247, 248: n + 248, 249: n + 249,
+        250: |a| a[249] + n, 251: n + 251, 252: n + 252, 253: n + 253, 254: n + 254, 255: n + 255, 256: n + 256, 257: n + 257, 258: n + 258, 259: n + 259,
+        260: n + 260, 261: n + 261, 262: n + 262, 263: n + 263, 264: n + 264, 265: n + 265, 266: n + 266, 267: n + 267, 268: n + 268, 269: n + 269,
+        270: n + 270, 271: n + 271, 272: n + 272, 273: n + 273, 274: n + 274, 275: n + 275, 276: n + 276, 277: n + 277, 278: n + 278, 279: n + 279,
+        280: n + 280, 281: n + 281, 282: n + 282, 283: n + 283, 284: n + 284, 285: n + 285, 286: n + 286, 287: n + 287, 288: n + 288, 289: n + 289,
+        290: n + 290, 291: n + 291, 292: n + 292, 293: n + 293, 294: n + 294, 295: n + 295, 296: n + 296, 297: n + 297, 298: n + 298, 299: n + 299,
+        300: n + 300, 301: n + 301, 302: n + 302, 303: n + 303, 304: n + 304, 305: n + 305, 306: n + 306, 307: n + 307, 308: n + 308, 309: n + 309,
+        310: n + 310, 311: n + 311, 312: n + 312, 313: n + 313, 314: n + 314, 315: n + 315, 316: n + 316, 317: n + 317, 318: n + 318, 319: n + 319,
+        320: n + 320, 321: n + 321, 322: n + 322, 323: n + 323, 324: n + 324, 325: n + 325, 326: n + 326, 327: n + 327, 328: n + 328, 329: n + 329,
+        330: n + 330, 331: n + 331, 332: n + 332, 333: n + 333, 334: n + 334, 335: n + 335, 336: n + 336, 337: n + 337, 338: n + 338, 339: n + 339,
+        340: n + 340, 341: n + 341, 342: n + 342, 343: n + 343, 344: n + 344, 345: n + 345, 346: n + 346, 347: n + 347, 348: n + 348, 349: n + 349,
+        350: n + 350, 351: n + 351, 352: n + 352, 353: n + 353, 354: n + 354, 355: n + 355, 356: n + 356, 357: n + 357, 358: n + 358, 359: n + 359,
+        360: n + 360, 361: n + 361, 362: n + 362, 363: n + 363, 364: n + 364, 365: n + 365, 366: n + 366, 367: n + 367, 368: n + 368, 369: n + 369,
+        370: n + 370, 371: n + 371, 372: n + 372, 373: n + 373, 374: n + 374, 375: n + 375, 376: n + 376, 377: n + 377, 378: n + 378, 379: n + 379,
+        380: n + 380, 381: n + 381, 382: n + 382, 383: n + 383, 384: n + 384, 385: n + 385, 386: n + 386, 387: n + 387, 388: n + 388, 389: n + 389,
+        390: n + 390, 391: n + 391, 392: n + 392, 393: n + 393, 394: n + 394, 395: n + 395, 396: n + 396, 397: n + 397, 398: n + 398, 399: n + 399,
+        400: n + 400, 401: n + 401, 402: n + 402, 403: n + 403, 404: n + 404, 405: n + 405, 406: n + 406, 407: n + 407, 408: n + 408, 409: n + 409,
+        410: n + 410, 411: n + 411, 412: n + 412, 413: n + 413, 414: n + 414, 415: n + 415, 416: n + 416, 417: n + 417, 418: n + 418, 419: n + 419,
+        420: n + 420, 421: n + 421, 422: n + 422, 423: n + 423, 424: n + 424, 425: n + 425, 426: n + 426, 427: n + 427, 428: n + 428, 429: n + 429,
+        430: n + 430, 431: n + 431, 432: n + 432, 433: n + 433, 434: n + 434, 435: n + 435, 436: n + 436, 437: n + 437, 438: n + 438, 439: n + 439,
+        440: n + 440, 441: n + 441, 442: n + 442, 443: n + 443, 444: n + 444, 445: n + 445, 446: n + 446, 447: n + 447, 448: n + 448, 449: n + 449,
+        450: n + 450, 451: n + 451, 452: n + 452, 453: n + 453, 454: n + 454, 455: n + 455, 456: n + 456, 457: n + 457, 458: n + 458, 459: n + 459,
+        460: n + 460, 461: n + 461, 462: n + 462, 463: n + 463, 464: n + 464, 465: n + 465, 466: n + 466, 467: n + 467, 468: n + 468, 469: n + 469,
+        470: n + 470, 471: n + 471, 472: n + 472, 473: n + 473, 474: n + 474, 475: n + 475, 476: n + 476, 477: n + 477, 478: n + 478, 479: n + 479,
+        480: n + 480, 481: n + 481, 482: n + 482, 483: n + 483, 484: n + 484, 485: n + 485, 486: n + 486, 487: n + 487, 488: n + 488, 489: n + 489,
+        490: n + 490, 491: n + 491, 492: n + 492, 493: n + 493, 494: n + 494, 495: n + 495, 496: n + 496, 497: n + 497, 498: n + 498, 499: n + 499,
+    }];
+    for (i, &x) in arr.iter().enumerate() {
+        assert_eq!(x, if i == 250 { 251 } else { i + 1 });
+    }
+}
+
+#[test]
+fn test_zeroed() {
+    assert_eq!(zeroed_arr![u8; 4], [0; 4]);
+    assert_eq!(zeroed_arr![i32; 4; { 0: 1, 2..4: -1 }], [1, 0, -1, -1]);
+    assert_eq!(zeroed_arr![f64; 2; { 1: 0.5 }], [0.0, 0.5]);
+    assert_eq!(zeroed_arr![bool; 2; { 1: true }], [false, true]);
+    assert_eq!(zeroed_arr![char; 2; { 0: 'a' }], ['a', '\0']);
+    assert_eq!(zeroed_arr![[u16; 2]; 2; { 1: [1, 2] }], [[0, 0], [1, 2]]);
+
+    let big = zeroed_arr![u64; 4096; { ^0: 1 }];
+    assert_eq!(big.iter().sum::<u64>(), 1);
+}
+
+#[test]
+fn test_named_array() {
+    // backward references see the values assigned before
+    let a = arr![0; 8; as a { 0: 3, 4: a[0] + 1, 5..7: a[4] * 2 }];
+    assert_eq!(a, [3, 0, 0, 0, 4, 8, 8, 0]);
+    // forward references see the fill value
+    let b = arr![1; 4; as b { 0: b[3] + 1, 3: 5 }];
+    assert_eq!(b, [2, 1, 1, 5]);
+    assert_eq!(arr![0; _; as x { 0: 1, 2: x[0] + x[1] }], [1, 0, 1]);
+    const LEN: usize = 3;
+    assert_eq!(arr![2; LEN; as x { ^0: x[0] * x[1] }], [2, 2, 4]);
+    // other kinds of entries are supported as well
+    let c = arr![0; 8; as c { [0]: [1, 2], [2]: [c[1], 3], 6: |d| d[5] + 1, base 7 { 0: 9 } }];
+    assert_eq!(c, [1, 2, 2, 3, 0, 0, 1, 9]);
+}
+
+#[test]
+fn test_entry_attributes() {
+    let a = arr![0; 4; {
+        #[cfg(feature = "std")] 0: 1,
+        #[cfg(not(feature = "std"))] 0: 2,
+        #[cfg(any())] 1..4: 5,
+        #[cfg(all())] #[allow(clippy::identity_op)] 3: 1 + 0,
+    }];
+    if cfg!(feature = "std") {
+        assert_eq!(a, [1, 0, 0, 1]);
+    } else {
+        assert_eq!(a, [2, 0, 0, 1]);
+    }
+    assert_eq!(arr![0; 3; { #[cfg(any())] 0: 1, 2: 2 }], [0, 0, 2]);
+    assert_eq!(arr![0; 3; as a { #[cfg(all())] 2: a[1] + 1 }], [0, 0, 1]);
+    assert_eq!(arr_strict![0; 2; { #[cfg(any())] 1: 1, 1: 2 }], [0, 2]);
+    const C: [i32; 2] = const_arr![0; 2; { #[cfg(any())] 0: 1, #[cfg(all())] 1: 2 }];
+    assert_eq!(C, [0, 2]);
+}
+
+#[test]
+fn test_from_slice() {
+    let src = [1, 2, 3, 4];
+    assert_eq!(arr_from_slice![src; 4], Ok([1, 2, 3, 4]));
+    assert_eq!(arr_from_slice![src[1..3]; 2], Ok([2, 3]));
+    assert_eq!(arr_from_slice![src[..0]; 0], Ok::<[i32; 0], _>([]));
+    // too short
+    assert_eq!(
+        arr_from_slice![src[..2]; 3],
+        Err(SliceLengthError {
+            len: 2,
+            expected: 3
+        }),
+    );
+    // too long
+    assert_eq!(
+        arr_from_slice![src; 3],
+        Err(SliceLengthError {
+            len: 4,
+            expected: 3
+        }),
+    );
+
+    // elements that aren't `Copy` are cloned
+    let cells = [Cell::new(1), Cell::new(2)];
+    assert_eq!(arr_from_slice![cells; 2], Ok(cells.clone()));
+    assert_eq!(
+        arr_from_slice![cells; 1],
+        Err::<[Cell<i32>; 1], _>(SliceLengthError {
+            len: 2,
+            expected: 1
+        }),
+    );
+}
+
+#[test]
+fn test_arr_range() {
+    assert_eq!(arr_range![3; 4], [3, 4, 5, 6]);
+    let a: [usize; 5] = arr_range![5];
+    assert_eq!(a, [0, 1, 2, 3, 4]);
+    assert_eq!(arr_range![0], [0_usize; 0]);
+    // the last element may be the maximum value of the type
+    assert_eq!(arr_range![253_u8; 3], [253, 254, 255]);
+    assert_eq!(arr_range![-2_i64; 3], [-2, -1, 0]);
+
+    const A: [u16; 4] = arr_range![10; 4];
+    assert_eq!(A, [10, 11, 12, 13]);
+}
+
+#[test]
+#[cfg(feature = "smallvec")]
+fn test_small_vec() {
+    use crate::small_vec;
+    use smallvec::SmallVec;
+
+    // the inline capacity is inferred from the annotation
+    let a: SmallVec<[i32; 8]> = small_vec![0; 4; { 1: 1, ^0: 3 }];
+    assert_eq!(&a[..], [0, 1, 0, 3]);
+    assert_eq!(a.inline_size(), 8);
+    assert!(!a.spilled());
+
+    let b: SmallVec<[u8; 2]> = small_vec![0; 4; { [1]: [1, 2] }];
+    assert_eq!(&b[..], [0, 1, 2, 0]);
+    assert!(b.spilled());
+
+    let c: SmallVec<[String; 4]> = small_vec!["a".to_string(), "b".to_string()];
+    assert_eq!(&c[..], ["a", "b"]);
+    let d: SmallVec<[u8; 4]> = small_vec![7; 3];
+    assert_eq!(&d[..], [7, 7, 7]);
+}
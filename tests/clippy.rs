@@ -0,0 +1,86 @@
+//! Uses the macros in a crate that denies all default clippy lints, so
+//! `cargo clippy --all-targets` fails if an expansion triggers one of them.
+//!
+//! Clippy skips most lints in code from another crate's macros, but not the
+//! ones that also look at the tokens passed to the macro, such as an index or
+//! a range. The expansions currently don't need any `#[allow]` for these.
+
+#![deny(clippy::all)]
+
+use array_lit::{arr, arr2d, arr_strict, arr_uninit, const_arr, try_arr};
+
+const TABLE: [u8; 8] = const_arr![0; 8; { 0: 1, 2..4: 2, [5]: [3, 4], ^0: 9, mirror }];
+
+#[test]
+fn arrays() {
+    let n = 3;
+    let offset = 1;
+    let src = [7, 8];
+    let a = arr![0; 8; {
+        0: 1,
+        1..n: 2,
+        n..=4: 3,
+        ..1: 4,
+        6..: 5,
+        ^0: 6,
+        1 | 3: 7,
+        [offset]: [1, 2],
+        [4]: [0; 2],
+        [5]: src,
+        in [2, 4]: 8,
+        0..8;2: fn |i| i,
+        rev 0..4: 9,
+        clamp 9: 1,
+        wrap 9: 2,
+        0..8 where |i| i > 4: 3,
+        0..8 if |x| *x == 0: 4,
+        1: |a| a[0] + 1,
+        2: += 1,
+    }];
+    assert_eq!(a.len(), 8);
+    assert_eq!(TABLE[7], 1);
+
+    let b = arr![1u8; 6; as a { 0: 2, 1..3: a[0] * 2, base 3 { 0: 1, 1..3: 0 } }];
+    assert_eq!(b, [2, 4, 4, 1, 0, 0]);
+    let c = arr_strict![0; 4; { 0: 1, 2 | 3: 2 }];
+    assert_eq!(c, [1, 0, 2, 2]);
+    let d = arr_uninit![2; { 0: 'a', 1: 'b' }];
+    assert_eq!(d, ['a', 'b']);
+    let e = try_arr![0; 2; { 0: 1, offset: 2 }];
+    assert_eq!(e, Ok([1, 2]));
+    let f = arr2d![0; 2, 2; { (0, 0): 1, (1,): [2, 3] }];
+    assert_eq!(f, [[1, 0], [2, 3]]);
+    let g = arr![fn |i| i * 2; 4; { 0: 1 }];
+    assert_eq!(g, [1, 2, 4, 6]);
+    let h = arr![_; 3; { ..: fn |i| i, 0: 9, mirror }];
+    assert_eq!(h, [9, 1, 9]);
+    let j = arr![0; _; { 0: 1, [3]: [2, 3] }];
+    assert_eq!(j, [1, 0, 0, 2, 3]);
+    let k = arr![1, 2; 4; 0];
+    assert_eq!(k, [1, 2, 0, 0]);
+    const LEN: usize = 3;
+    let l = arr![1, offset; LEN; 0];
+    assert_eq!(l, [1, 1, 0]);
+    let m = arr![(0, 0); 2; { 0 .1: 1, (offset).0: 2, 1 .1: += 3 }];
+    assert_eq!(m, [(0, 1), (2, 3)]);
+    let o = arr![0; 3; { 1: 1 } : u64];
+    assert_eq!(o[1].leading_zeros(), 63);
+    let p = arr![0; 4; { 1..4: |prev, i| prev + i, "first" 0: 1 }];
+    assert_eq!(p, [1, 1, 3, 6]);
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "alloc"))]
+fn vecs() {
+    use array_lit::vec;
+
+    let n = 4;
+    let a = vec![0; n; { 0: 1, 1..n: 2, [2]: [3, 4], 0..n if |x| *x == 2: 5 }];
+    assert_eq!(a, [1, 5, 3, 4]);
+    let b = vec![0; auto; { 0: 1, [2]: [2, 3] }];
+    assert_eq!(b, [1, 0, 2, 3]);
+    let c = vec![from a; { 0: 9 }];
+    assert_eq!(c, [9, 5, 3, 4]);
+    let d = vec![0; n; { 0: 1, remove 1, remove n - 1 }];
+    assert_eq!(d, [1, 0]);
+}
@@ -0,0 +1,61 @@
+//! Round-trips sparse arrays through JSON. This needs `serde_json`, which
+//! would make some type inference in the unit tests ambiguous, so these tests
+//! are in a separate crate.
+
+#![cfg(feature = "serde")]
+
+use array_lit::sparse::SparseArray;
+use array_lit::{arr, IndexError};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn json_round_trip() {
+    let a = arr![0; 8; { 3: 1, 6..8: 2 }];
+    let sparse = SparseArray::from_slice(0, &a);
+    let json = serde_json::to_string(&sparse).unwrap();
+    assert_eq!(json, r#"{"default":0,"overrides":{"3":1,"6":2,"7":2}}"#);
+    let back: SparseArray<i32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, sparse);
+    assert_eq!(back.to_array(), Ok(a));
+    assert_eq!(back.to_vec(9).unwrap(), [0, 0, 0, 1, 0, 0, 2, 2, 0]);
+}
+
+#[test]
+fn missing_overrides() {
+    let sparse: SparseArray<u8> = serde_json::from_str(r#"{"default": 5}"#).unwrap();
+    assert!(sparse.overrides.is_empty());
+    assert_eq!(sparse.to_array(), Ok([5; 3]));
+}
+
+#[test]
+fn override_out_of_bounds() {
+    let sparse: SparseArray<u8> =
+        serde_json::from_str(r#"{"default": 0, "overrides": {"1": 1, "7": 2}}"#).unwrap();
+    assert_eq!(sparse.to_array::<7>(), Err(IndexError { index: 7, len: 7 }));
+    assert_eq!(sparse.to_vec(8).unwrap(), [0, 1, 0, 0, 0, 0, 0, 2]);
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Config {
+    #[serde(
+        serialize_with = "array_lit::sparse::serialize",
+        deserialize_with = "array_lit::sparse::deserialize"
+    )]
+    table: [u8; 6],
+}
+
+#[test]
+fn struct_field() {
+    let config = Config {
+        table: arr![0; 6; { 1: 4, ^0: 9 }],
+    };
+    let json = serde_json::to_string(&config).unwrap();
+    assert_eq!(json, r#"{"table":{"default":0,"overrides":{"1":4,"5":9}}}"#);
+    assert_eq!(serde_json::from_str::<Config>(&json).unwrap(), config);
+
+    let err = serde_json::from_str::<Config>(r#"{"table":{"default":1,"overrides":{"6":2}}}"#)
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("index 6 is out of bounds for length 6"));
+}
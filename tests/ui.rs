@@ -0,0 +1,91 @@
+//! Compiles every file in `tests/ui` against this crate and checks that it
+//! fails with the errors listed in the `.stderr` file next to it.
+//!
+//! Only the `error` lines are compared, since the rest of the output depends
+//! on line numbers in `src/lib.rs`. Run with `UI_BLESS=1` to overwrite the
+//! `.stderr` files with the actual errors.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const FEATURES: &[(&str, bool)] = &[
+    ("std", cfg!(feature = "std")),
+    ("alloc", cfg!(feature = "alloc")),
+    ("check-overlap", cfg!(feature = "check-overlap")),
+    ("sorted-indices", cfg!(feature = "sorted-indices")),
+];
+
+fn rustc() -> Command {
+    let mut cmd = Command::new(env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string()));
+    cmd.current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(["--edition", "2018", "--color", "never"]);
+    cmd
+}
+
+fn build_lib(out_dir: &Path) -> PathBuf {
+    let mut cmd = rustc();
+    cmd.args(["--crate-type", "lib", "--crate-name", "array_lit"])
+        .args(["src/lib.rs", "--out-dir"])
+        .arg(out_dir);
+    for &(feature, enabled) in FEATURES {
+        if enabled {
+            cmd.args(["--cfg", &format!("feature=\"{}\"", feature)]);
+        }
+    }
+    let output = cmd.output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    out_dir.join("libarray_lit.rlib")
+}
+
+fn errors(stderr: &str) -> String {
+    stderr
+        .lines()
+        .filter(|line| line.starts_with("error") && !line.starts_with("error: aborting"))
+        .map(|line| format!("{}\n", line))
+        .collect()
+}
+
+#[test]
+fn ui() {
+    let out_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("ui");
+    fs::create_dir_all(&out_dir).unwrap();
+    let lib = build_lib(&out_dir);
+    let bless = env::var_os("UI_BLESS").is_some();
+
+    let mut cases: Vec<_> = fs::read_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/ui"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension() == Some("rs".as_ref()))
+        .collect();
+    cases.sort();
+
+    let mut failures = Vec::new();
+    for case in cases {
+        let output = rustc()
+            .args(["--crate-type", "lib", "--emit", "metadata"])
+            .arg("--extern")
+            .arg(format!("array_lit={}", lib.display()))
+            .arg("--out-dir")
+            .arg(&out_dir)
+            .arg(&case)
+            .output()
+            .unwrap();
+        let actual = errors(&String::from_utf8_lossy(&output.stderr));
+        let stderr_file = case.with_extension("stderr");
+
+        if bless {
+            fs::write(&stderr_file, &actual).unwrap();
+        } else if output.status.success() {
+            failures.push(format!("{} compiled successfully", case.display()));
+        } else if fs::read_to_string(&stderr_file).ok().as_deref() != Some(actual.as_str()) {
+            failures.push(format!("{} failed with:\n{}", case.display(), actual));
+        }
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
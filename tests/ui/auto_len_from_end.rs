@@ -0,0 +1,5 @@
+use array_lit::vec;
+
+pub fn auto_len_from_end() -> Vec<i32> {
+    vec![0; auto; { ^0: 1 }]
+}
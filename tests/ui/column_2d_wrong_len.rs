@@ -0,0 +1,5 @@
+use array_lit::arr2d;
+
+pub fn column_2d_wrong_len() -> [[i32; 4]; 3] {
+    arr2d![0; 3, 4; { col 1: [1, 2] }]
+}
@@ -0,0 +1,5 @@
+use array_lit::arr;
+
+pub fn compound_unsupported() -> [u8; 4] {
+    arr![1; 4; { clamp 5: *= 2 }]
+}
@@ -0,0 +1,5 @@
+use array_lit::arr_concat;
+
+pub fn concat_not_array() -> [u8; 3] {
+    arr_concat![[1, 2], 3]
+}
@@ -0,0 +1,4 @@
+use array_lit::arr;
+
+const HEADER: [i32; 2] = [1, 2];
+pub const ARR: [i32; 4] = arr![0; 4; { [0]: HEADER }];
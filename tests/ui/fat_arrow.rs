@@ -0,0 +1,5 @@
+use array_lit::arr;
+
+pub fn fat_arrow() -> [i32; 4] {
+    arr![0; 4; { 0 => 1 }]
+}
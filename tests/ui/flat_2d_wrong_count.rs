@@ -0,0 +1,5 @@
+use array_lit::arr2d_flat;
+
+pub fn flat_2d_wrong_count() -> [[i32; 3]; 2] {
+    arr2d_flat![0; 2, 3; [1, 2, 3, 4, 5]]
+}
@@ -0,0 +1,5 @@
+use array_lit::arr;
+
+pub fn guard_missing_colon() -> [u8; 4] {
+    arr![0; 4; { 0..4 where |i| i > 1, 0: 1 }]
+}
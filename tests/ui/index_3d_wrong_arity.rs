@@ -0,0 +1,5 @@
+use array_lit::arr3d;
+
+pub fn index_3d_wrong_arity() -> [[[i32; 2]; 2]; 2] {
+    arr3d![0; 2, 2, 2; { (1, 0): 9 }]
+}
@@ -0,0 +1,5 @@
+use array_lit::arr;
+
+pub fn index_fn_unsupported() -> [usize; 4] {
+    arr![0; 4; { clamp 5: fn |i| i }]
+}
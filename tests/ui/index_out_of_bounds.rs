@@ -0,0 +1,5 @@
+use array_lit::arr;
+
+pub fn index_out_of_bounds() -> [i32; 4] {
+    arr![0; 4; { 7: 1 }]
+}
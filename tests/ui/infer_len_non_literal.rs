@@ -0,0 +1,6 @@
+use array_lit::arr;
+
+pub fn infer_len_non_literal() {
+    let i = 4;
+    let _ = arr![0; _; { 1: 1, i: 2 }];
+}
@@ -0,0 +1,5 @@
+use array_lit::arr;
+
+pub fn omitted_fill_partial_cover() -> [String; 2] {
+    arr![_; 2; { 0..2: String::new() }]
+}
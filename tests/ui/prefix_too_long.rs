@@ -0,0 +1,5 @@
+use array_lit::arr;
+
+pub fn prefix_too_long() -> [i32; 2] {
+    arr![1, 2, 3; 2; 0]
+}
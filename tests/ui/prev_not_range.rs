@@ -0,0 +1,5 @@
+use array_lit::arr;
+
+pub fn prev_not_range() -> [i32; 4] {
+    arr![0; 4; { 2: |prev, i| prev + i as i32 }]
+}
@@ -0,0 +1,5 @@
+use array_lit::vec;
+
+pub fn remove_not_last() -> Vec<i32> {
+    vec![0; 4; { remove 1, 0: 1 }]
+}
@@ -0,0 +1,5 @@
+use array_lit::arr_strict;
+
+pub fn strict_duplicate_index() -> [i32; 4] {
+    arr_strict![0; 4; { 0: 1, 2 | 3: 5, 3: 2 }]
+}
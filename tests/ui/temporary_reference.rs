@@ -0,0 +1,5 @@
+use array_lit::arr;
+
+pub fn return_temporary() -> &'static [i32; 4] {
+    &arr![0; 4; { 0: 1 }]
+}
@@ -0,0 +1,5 @@
+use array_lit::arr_transpose;
+
+pub fn transpose_wrong_row_len() -> [[i32; 2]; 3] {
+    arr_transpose![0; 2, 3; rows [[1, 2, 3], [4, 5]]]
+}
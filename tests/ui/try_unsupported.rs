@@ -0,0 +1,5 @@
+use array_lit::try_arr;
+
+pub fn try_unsupported() {
+    let _ = try_arr![0; 4; as a { 1: a[0] }];
+}
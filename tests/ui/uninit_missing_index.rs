@@ -0,0 +1,5 @@
+use array_lit::arr_uninit;
+
+pub fn uninit_missing_index() -> [char; 3] {
+    arr_uninit![3; { 0: 'a', 2: 'c' }]
+}
@@ -0,0 +1,5 @@
+use array_lit::zeroed_arr;
+
+pub fn zeroed_not_zeroable() -> [&'static u8; 4] {
+    zeroed_arr![&'static u8; 4]
+}